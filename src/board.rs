@@ -0,0 +1,321 @@
+//! # Boards
+//! Boards are laid out row by row, with `0` for the blank. This module has the pieces of the puzzle that
+//! only need a board, not a search - where the blank can move, whether a goal can be reached, and how to
+//! print one.
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// # Inversion parity of a board, used to tell whether the goal can be reached.
+/// ## Properties
+///- `inversions`:  Number of pairs of tiles (ignoring the blank) that appear out of order.
+///- `blank_row`:   Row of the blank, counted from the top starting at 0.
+///- `odd`:         Parity of `inversions` on odd-width boards, or of `inversions + blank_row` on even-width boards.
+/// ## Further details
+/// Sliding a tile left or right never changes the number of inversions. Sliding it up or down moves it
+/// past `width - 1` other tiles, which flips the inversion parity when the width is even, and also moves
+/// the blank by one row. Either way the invariant is preserved, so two boards with different invariants
+/// can never be reached from one another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Parity {
+    pub inversions: usize,
+    pub blank_row: usize,
+    pub odd: bool,
+}
+
+impl Parity {
+    /// Computes the parity of a board laid out row by row, `width` tiles per row.
+    pub fn of(board: &[isize], width: usize) -> Parity {
+        let tiles: Vec<isize> = board.iter().cloned().filter(|&x| x != 0).collect();
+        let mut inversions = 0;
+        for (i, a) in tiles.iter().enumerate() {
+            inversions += tiles[i + 1..].iter().filter(|&b| b < a).count();
+        }
+        let blank_row = board.iter().position(|&x| x == 0).unwrap_or(0) / width;
+
+        let invariant = if width % 2 == 1 {
+            inversions
+        } else {
+            inversions + blank_row
+        };
+
+        Parity {
+            inversions,
+            blank_row,
+            odd: invariant % 2 == 1,
+        }
+    }
+}
+
+impl fmt::Display for Parity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} inversions, blank on row {} ({})",
+            self.inversions,
+            self.blank_row,
+            if self.odd { "odd" } else { "even" }
+        )
+    }
+}
+
+/// # A move of the blank, one cell up, down, left or right.
+/// Stored in a single byte, so search nodes can keep the move that led to them cheaply.
+/// Displays as the direction the blank moves, like "Up". See `Notation` for the other ways to write it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Move {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Move {
+    /// Every move, in the order `neighbours` lists them.
+    pub const ALL: [Move; 4] = [Move::Up, Move::Down, Move::Left, Move::Right];
+
+    /// Name used when printing, like "Up".
+    pub fn name(self) -> &'static str {
+        self.notate(Notation::Blank)
+    }
+
+    /// The move that undoes this one.
+    pub fn inverse(self) -> Move {
+        match self {
+            Move::Up => Move::Down,
+            Move::Down => Move::Up,
+            Move::Left => Move::Right,
+            Move::Right => Move::Left,
+        }
+    }
+
+    /// Writes the move in the given notation.
+    pub fn notate(self, notation: Notation) -> &'static str {
+        let m = match notation {
+            Notation::Blank | Notation::BlankLetters => self,
+            Notation::Tile | Notation::TileLetters => self.inverse(),
+        };
+        let (word, letter) = match m {
+            Move::Up => ("Up", "U"),
+            Move::Down => ("Down", "D"),
+            Move::Left => ("Left", "L"),
+            Move::Right => ("Right", "R"),
+        };
+        match notation {
+            Notation::Blank | Notation::Tile => word,
+            Notation::BlankLetters | Notation::TileLetters => letter,
+        }
+    }
+
+    /// Cell the blank at `loc` ends up in, on a board `cols` wide with `len` cells, if it stays on the board.
+    pub fn apply(self, loc: usize, cols: usize, len: usize) -> Option<usize> {
+        neighbours(loc, cols, len)[self as usize].map(|(next, _)| next)
+    }
+}
+
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Reads a move of the blank, written as a word like "up" or a letter like "U", in any case.
+impl FromStr for Move {
+    type Err = ParseMoveError;
+
+    fn from_str(s: &str) -> Result<Move, ParseMoveError> {
+        match s.trim().to_lowercase().as_str() {
+            "up" | "u" => Ok(Move::Up),
+            "down" | "d" => Ok(Move::Down),
+            "left" | "l" => Ok(Move::Left),
+            "right" | "r" => Ok(Move::Right),
+            _ => Err(ParseMoveError(s.to_string())),
+        }
+    }
+}
+
+/// # Ways to write a move.
+/// Moves can be described by where the blank goes, or by where the tile that slides into it goes, which is
+/// always the opposite direction. The blank moving up is the tile above it moving down.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notation {
+    /// The direction the blank moves, as a word like "Up". This is how moves are displayed.
+    Blank,
+    /// The direction the blank moves, as a letter like "U".
+    BlankLetters,
+    /// The direction the tile moves, as a word like "Down" when the blank moves up.
+    Tile,
+    /// The direction the tile moves, as a letter like "D" when the blank moves up.
+    TileLetters,
+}
+
+impl Notation {
+    /// Reads a single move written in this notation.
+    pub fn parse(self, s: &str) -> Result<Move, ParseMoveError> {
+        let m: Move = s.parse()?;
+        Ok(match self {
+            Notation::Blank | Notation::BlankLetters => m,
+            Notation::Tile | Notation::TileLetters => m.inverse(),
+        })
+    }
+
+    /// Reads a sequence of moves written in this notation, separated by commas or spaces.
+    /// Letters may also be run together, like "ULDR".
+    pub fn parse_all(self, s: &str) -> Result<Vec<Move>, ParseMoveError> {
+        let mut moves = Vec::new();
+        for word in s.split(|c: char| c == ',' || c.is_whitespace()).filter(|w| !w.is_empty()) {
+            match self.parse(word) {
+                Ok(m) => moves.push(m),
+                Err(e) => {
+                    if !word.chars().all(|c| "udlrUDLR".contains(c)) {
+                        return Err(e);
+                    }
+                    for c in word.chars() {
+                        moves.push(self.parse(c.encode_utf8(&mut [0; 4]))?);
+                    }
+                }
+            }
+        }
+        Ok(moves)
+    }
+
+    /// Writes a sequence of moves in this notation. Letters are run together, words are separated by
+    /// spaces.
+    pub fn write_all(self, moves: &[Move]) -> String {
+        let sep = match self {
+            Notation::Blank | Notation::Tile => " ",
+            Notation::BlankLetters | Notation::TileLetters => "",
+        };
+        let names: Vec<&str> = moves.iter().map(|m| m.notate(self)).collect();
+        names.join(sep)
+    }
+}
+
+/// Text that isn't a move.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMoveError(pub String);
+
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "`{}` isn't a move, expected up, down, left or right.", self.0)
+    }
+}
+
+impl Error for ParseMoveError {}
+
+/// Lists the cells the blank at `loc` can move to, on a board `cols` wide with `len` cells.
+/// Each comes with the move that gets it there, in the order Up, Down, Left, Right.
+pub fn neighbours(loc: usize, cols: usize, len: usize) -> [Option<(usize, Move)>; 4] {
+    let mut moves = [None; 4];
+    if loc >= cols {
+        moves[0] = Some((loc - cols, Move::Up));
+    }
+    if loc + cols < len {
+        moves[1] = Some((loc + cols, Move::Down));
+    }
+    if !loc.is_multiple_of(cols) {
+        moves[2] = Some((loc - 1, Move::Left));
+    }
+    if loc % cols != cols - 1 {
+        moves[3] = Some((loc + 1, Move::Right));
+    }
+    moves
+}
+
+/// Finds the row and column of a number in a board split into rows. 
+pub fn find(_vec: &Vec<&[isize]>, r: &isize) -> (isize, isize) {
+    let mut x = 0;
+    let mut y = 0;
+    for (i, row) in _vec.iter().enumerate() {
+        if row.contains(r) {
+            x = i;
+        }
+        for (j, val) in row.iter().enumerate() {
+            if *val == *r {
+                y = j;
+            }
+        }
+    }
+    (x as isize, y as isize)
+}
+
+/// # A board laid out in rows, for printing.
+/// ## Properties
+///- `tiles`:   The tiles on the board, row by row.
+///- `cols`:    Number of columns on the board.
+pub struct Grid<'a> {
+    pub tiles: &'a [isize],
+    pub cols: usize,
+}
+
+impl<'a> fmt::Display for Grid<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self.tiles.iter().max().unwrap_or(&0).to_string().len();
+        let border = "-".repeat(self.cols * (width + 3) + 1);
+
+        writeln!(f, "{}", border)?;
+        for row in self.tiles.chunks(self.cols) {
+            for tile in row {
+                write!(f, "| {:>width$} ", tile, width = width)?;
+            }
+            writeln!(f, "|")?;
+        }
+        write!(f, "{}", border)
+    }
+}
+
+/// # A board packed into a single integer, five bits per cell.
+/// Cell `i` holds its tile in bits `5 * i` to `5 * i + 4`, so boards of up to 25 cells (the 5x5 puzzle)
+/// fit, and copying, hashing or comparing one never touches the heap.
+/// ## Further details
+/// Only the tiles are stored, not how many there are or how wide the board is, so the cells past the end
+/// of a board are always `0`. Moving a tile swaps two cells, which is a couple of shifts and XORs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Board(u128);
+
+impl Board {
+    /// Largest number of cells a board can have.
+    pub const MAX_CELLS: usize = 25;
+    const BITS: usize = 5;
+    const MASK: u128 = (1 << Board::BITS) - 1;
+
+    /// Packs a board laid out row by row. Returns `None` if it has more than `MAX_CELLS` cells, or a tile
+    /// outside `0..MAX_CELLS`.
+    pub fn pack(tiles: &[isize]) -> Option<Board> {
+        if tiles.len() > Board::MAX_CELLS {
+            return None;
+        }
+        let mut bits = 0;
+        for (i, &t) in tiles.iter().enumerate() {
+            if t < 0 || t as usize >= Board::MAX_CELLS {
+                return None;
+            }
+            bits |= (t as u128) << (i * Board::BITS);
+        }
+        Some(Board(bits))
+    }
+
+    /// The tile in cell `i`.
+    pub fn get(&self, i: usize) -> isize {
+        ((self.0 >> (i * Board::BITS)) & Board::MASK) as isize
+    }
+
+    /// Swaps the tiles in cells `i` and `j`.
+    pub fn swap(&mut self, i: usize, j: usize) {
+        let diff = ((self.0 >> (i * Board::BITS)) ^ (self.0 >> (j * Board::BITS))) & Board::MASK;
+        self.0 ^= (diff << (i * Board::BITS)) | (diff << (j * Board::BITS));
+    }
+
+    /// Writes the first `tiles.len()` cells into `tiles`, row by row.
+    pub fn unpack_into(&self, tiles: &mut [isize]) {
+        for (i, t) in tiles.iter_mut().enumerate() {
+            *t = self.get(i);
+        }
+    }
+
+    /// The first `len` cells, row by row.
+    pub fn unpack(&self, len: usize) -> Vec<isize> {
+        (0..len).map(|i| self.get(i)).collect()
+    }
+}
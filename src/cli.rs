@@ -0,0 +1,864 @@
+//! # Command-line interface
+//! Run without arguments, or with `demo`, to solve the examples from the documentation.
+//! Run with `help` for the other commands and their options.
+use project_1_itcs_6156::{
+    board, Algorithm, Board, DistanceTable, Goal, Hamming, Heuristic, Limits, LinearConflict, Manhattan, Move,
+    Notation, Parity, PatternDatabase, PatternDbError, Problem, Solution, SolveError,
+};
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, Read};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+const USAGE: &str = "\
+Usage: project_1_itcs_6156 <command> [options]
+
+Commands:
+  solve [START] [GOAL]   Solve a board and print the moves.
+  check [START] [GOAL]   Check that a board is valid and solvable, without searching.
+  generate               Print random solvable boards, one per line.
+  bench                  Solve random boards and report how much work it took.
+  demo                   Solve the examples from the documentation (the default).
+  help                   Print this message.
+
+Boards are lists of tiles, row by row, separated by commas or spaces, with 0 for the blank.
+The goal defaults to the tiles in order with the blank last.
+  --start BOARD          Initial state, instead of the first argument.
+  --goal BOARD           Goal state, instead of the second argument.
+  --file PATH            Read the initial state from the first line of a file, and the goal from the
+                         second line if there is one. Use - to read from stdin.
+  --rows N, --cols N     Size of the board. Defaults to a square just big enough for the tiles,
+                         or 3x3 for generate and bench.
+
+Searching:
+  --algorithm NAME       astar (default), ida, rbfs (recursive best first), sma (memory-bounded A*,
+                         needs --max-nodes), mm (bidirectional), weighted (weighted A*, needs
+                         --weight), ara (anytime weighted A*, needs --weight), greedy, beam (needs
+                         --width), or one that ignores the heuristic - bfs (breadth first), dfs (depth
+                         first, needs --depth), iddfs (iterative deepening) or ucs (uniform cost).
+  --weight W             Multiply the heuristic by W, at least 1, for weighted. Solutions are at most W
+                         times longer than the shortest. For ara, the weight to start from.
+  --width K              Keep the K best nodes of every layer, for beam.
+  --depth N              Follow paths of at most N moves, for dfs.
+  --max-nodes N          Keep at most N nodes in memory, for sma.
+  --heuristic NAME       manhattan (default), hamming, linear-conflict, pdb, or table for boards of up to
+                         10 cells.
+  --partition SIZES      Sizes of the pattern database groups, like 5,5,5. Defaults to groups of 5.
+  --pdb PATH             Keep the pattern database in this file between runs.
+  --reopen               Let A* expand a board again when it finds a cheaper path to it. Needed for
+                         shortest solutions with heuristics that aren't consistent, like pdb.
+  --max-expanded N       Give up after expanding N nodes.
+  --timeout SECONDS      Give up after searching for this long. ara stops with the best solution so far.
+
+Output:
+  --format FORMAT        human (default) or json.
+  --notation NAME        How to write moves - blank (Up, the default), letters (U), tile (the direction the
+                         tile moves, Down when the blank moves up) or tile-letters (D).
+
+Random boards:
+  --count N              How many boards to generate or solve. Defaults to 1 for generate, 10 for bench.
+  --moves N              Scramble the goal with N random moves, instead of shuffling it completely.
+  --seed N               Seed for the random number generator. Defaults to the current time.
+
+Exit codes:
+  0  Solved, or the board is solvable.
+  1  The board can't be solved.
+  2  Invalid arguments or boards.
+  3  The search gave up before finding a solution.
+  4  A file couldn't be read or written.
+";
+
+/// Exit code for a solved or solvable board.
+const OK: i32 = 0;
+/// Exit code for a board that can't reach the goal.
+const UNSOLVABLE: i32 = 1;
+/// Exit code for invalid arguments or boards.
+const INVALID: i32 = 2;
+/// Exit code for a search that ran into a limit, or ran out of states.
+const GAVE_UP: i32 = 3;
+/// Exit code for a file that couldn't be read or written.
+const IO_ERROR: i32 = 4;
+
+/// Output formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Human,
+    Json,
+}
+
+/// # Everything that can be set from the command line.
+/// ## Properties
+///- `positional`:  Arguments that aren't options, i.e. boards.
+///- `start`, `goal`, `file`, `rows`, `cols`: Where the boards come from, and their size.
+///- `algorithm`, `weight`, `width`, `depth`, `max_nodes`, `heuristic`, `partition`, `pdb`, `limits`, `reopen`: How to search.
+///- `format`, `notation`: How to print results.
+///- `count`, `moves`, `seed`: How to make random boards.
+struct Options {
+    positional: Vec<String>,
+    start: Option<String>,
+    goal: Option<String>,
+    file: Option<String>,
+    rows: Option<usize>,
+    cols: Option<usize>,
+    algorithm: Algorithm,
+    weight: Option<f64>,
+    width: Option<usize>,
+    depth: Option<usize>,
+    max_nodes: Option<usize>,
+    heuristic: String,
+    partition: Option<Vec<usize>>,
+    pdb: Option<String>,
+    limits: Limits,
+    reopen: bool,
+    format: Format,
+    notation: Option<Notation>,
+    count: Option<usize>,
+    moves: Option<usize>,
+    seed: Option<u64>,
+}
+
+/// An error to report before exiting with `code`.
+struct Failure {
+    code: i32,
+    message: String,
+}
+
+impl Failure {
+    fn invalid(message: impl Into<String>) -> Failure {
+        Failure {
+            code: INVALID,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<SolveError> for Failure {
+    fn from(e: SolveError) -> Failure {
+        Failure {
+            code: exit_code(&e),
+            message: e.to_string(),
+        }
+    }
+}
+
+/// Runs the command given by `args`, which don't include the program name.
+/// Returns the exit code.
+pub fn main(args: &[String]) -> i32 {
+    let (command, rest) = match args.split_first() {
+        Some((command, rest)) => (command.as_str(), rest),
+        None => ("demo", args),
+    };
+    if let "help" | "--help" | "-h" = command {
+        print!("{}", USAGE);
+        return OK;
+    }
+
+    let options = match parse(rest) {
+        Ok(options) => options,
+        Err(f) => return report(f, Format::Human),
+    };
+    let format = options.format;
+    let result = match command {
+        "solve" => solve(&options),
+        "check" => check(&options),
+        "generate" => generate(&options),
+        "bench" => bench(&options),
+        "demo" => {
+            crate::demo();
+            Ok(OK)
+        }
+        _ => Err(Failure::invalid(format!(
+            "Unknown command `{}`. Run with `help` to list the commands.",
+            command
+        ))),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(f) => report(f, format),
+    }
+}
+
+/// Prints a failure to stderr, or as JSON to stdout, and returns its exit code.
+fn report(f: Failure, format: Format) -> i32 {
+    match format {
+        Format::Human => eprintln!("{}", f.message),
+        Format::Json => println!(
+            "{{\"status\":{},\"error\":{}}}",
+            json_str(status(f.code)),
+            json_str(&f.message)
+        ),
+    }
+    f.code
+}
+
+/// Exit code for each kind of `SolveError`.
+fn exit_code(e: &SolveError) -> i32 {
+    match e {
+        SolveError::Unsolvable(_) => UNSOLVABLE,
+        SolveError::LimitExceeded { .. } | SolveError::Exhausted(_) => GAVE_UP,
+        _ => INVALID,
+    }
+}
+
+/// Name of the outcome each exit code stands for, used in JSON output.
+fn status(code: i32) -> &'static str {
+    match code {
+        OK => "ok",
+        UNSOLVABLE => "unsolvable",
+        GAVE_UP => "gave_up",
+        IO_ERROR => "io_error",
+        _ => "invalid",
+    }
+}
+
+/// Reads options and positional arguments. Options may be written `--name value` or `--name=value`.
+fn parse(args: &[String]) -> Result<Options, Failure> {
+    let mut options = Options {
+        positional: Vec::new(),
+        start: None,
+        goal: None,
+        file: None,
+        rows: None,
+        cols: None,
+        algorithm: Algorithm::AStar,
+        weight: None,
+        width: None,
+        depth: None,
+        max_nodes: None,
+        heuristic: String::from("manhattan"),
+        partition: None,
+        pdb: None,
+        limits: Limits::default(),
+        reopen: false,
+        format: Format::Human,
+        notation: None,
+        count: None,
+        moves: None,
+        seed: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if !arg.starts_with("--") {
+            options.positional.push(arg.clone());
+            continue;
+        }
+        // the only option that doesn't take a value
+        if arg == "--reopen" {
+            options.reopen = true;
+            continue;
+        }
+        let (name, value) = match arg.find('=') {
+            Some(i) => (&arg[..i], arg[i + 1..].to_string()),
+            None => {
+                let value = args
+                    .next()
+                    .ok_or_else(|| Failure::invalid(format!("Option {} needs a value.", arg)))?;
+                (arg.as_str(), value.clone())
+            }
+        };
+
+        match name {
+            "--start" => options.start = Some(value),
+            "--goal" => options.goal = Some(value),
+            "--file" => options.file = Some(value),
+            "--rows" => options.rows = Some(number(name, &value)?),
+            "--cols" => options.cols = Some(number(name, &value)?),
+            "--algorithm" => options.algorithm = algorithm(&value)?,
+            "--weight" => {
+                let weight = value.parse().map_err(|_| {
+                    Failure::invalid(format!("{} needs a number, got `{}`.", name, value))
+                })?;
+                options.weight = Some(weight);
+            }
+            "--width" => options.width = Some(number(name, &value)?),
+            "--depth" => options.depth = Some(number(name, &value)?),
+            "--max-nodes" => options.max_nodes = Some(number(name, &value)?),
+            "--heuristic" => options.heuristic = value.to_lowercase(),
+            "--partition" => {
+                let sizes = value
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|s| !s.is_empty())
+                    .map(|s| number(name, s))
+                    .collect::<Result<_, _>>()?;
+                options.partition = Some(sizes);
+            }
+            "--pdb" => options.pdb = Some(value),
+            "--max-expanded" => options.limits.max_expanded = Some(number(name, &value)?),
+            "--timeout" => {
+                let secs: f64 = value
+                    .parse()
+                    .ok()
+                    .filter(|s: &f64| s.is_finite() && *s >= 0.0)
+                    .ok_or_else(|| {
+                        Failure::invalid(format!("{} needs a number of seconds.", name))
+                    })?;
+                options.limits.max_time = Some(Duration::from_secs_f64(secs));
+            }
+            "--format" => {
+                options.format = match value.as_str() {
+                    "human" => Format::Human,
+                    "json" => Format::Json,
+                    _ => return Err(Failure::invalid(format!("Unknown format `{}`.", value))),
+                }
+            }
+            "--notation" => {
+                options.notation = Some(match value.as_str() {
+                    "blank" => Notation::Blank,
+                    "letters" => Notation::BlankLetters,
+                    "tile" => Notation::Tile,
+                    "tile-letters" => Notation::TileLetters,
+                    _ => return Err(Failure::invalid(format!("Unknown notation `{}`.", value))),
+                })
+            }
+            "--count" => options.count = Some(number(name, &value)?),
+            "--moves" => options.moves = Some(number(name, &value)?),
+            "--seed" => options.seed = Some(number(name, &value)? as u64),
+            _ => return Err(Failure::invalid(format!("Unknown option {}.", name))),
+        }
+    }
+
+    // the depth, budget, weight and width may come before or after the algorithm
+    match (&mut options.algorithm, options.depth) {
+        (Algorithm::DepthFirst { limit }, Some(depth)) => *limit = depth,
+        (Algorithm::DepthFirst { .. }, None) => {
+            return Err(Failure::invalid("--algorithm dfs needs a --depth."))
+        }
+        (_, Some(_)) => return Err(Failure::invalid("--depth only applies to --algorithm dfs.")),
+        (_, None) => {}
+    }
+    match (&mut options.algorithm, options.max_nodes) {
+        (Algorithm::SmaStar { max_nodes }, Some(n)) => *max_nodes = n,
+        (Algorithm::SmaStar { .. }, None) => {
+            return Err(Failure::invalid("--algorithm sma needs a --max-nodes."))
+        }
+        (_, Some(_)) => {
+            return Err(Failure::invalid("--max-nodes only applies to --algorithm sma."))
+        }
+        (_, None) => {}
+    }
+    match (&mut options.algorithm, options.weight) {
+        (Algorithm::WeightedAStar { weight } | Algorithm::AraStar { weight }, Some(w)) => *weight = w,
+        (Algorithm::WeightedAStar { .. }, None) => {
+            return Err(Failure::invalid("--algorithm weighted needs a --weight."))
+        }
+        (Algorithm::AraStar { .. }, None) => {
+            return Err(Failure::invalid("--algorithm ara needs a --weight to start from."))
+        }
+        (_, Some(_)) => {
+            return Err(Failure::invalid("--weight only applies to --algorithm weighted or ara."))
+        }
+        (_, None) => {}
+    }
+    match (&mut options.algorithm, options.width) {
+        (Algorithm::Beam { width }, Some(w)) => *width = w,
+        (Algorithm::Beam { .. }, None) => {
+            return Err(Failure::invalid("--algorithm beam needs a --width."))
+        }
+        (_, Some(_)) => return Err(Failure::invalid("--width only applies to --algorithm beam.")),
+        (_, None) => {}
+    }
+    // every command works out the goal from the size of the board, so a size too large to hold is turned away
+    // before any board is read or any table built. Given one side, `generate` and `bench` make the board square,
+    // which `random_dimensions` checks again.
+    let cells = |rows: usize, cols: usize| rows.checked_mul(cols).filter(|&cells| cells <= Board::MAX_CELLS);
+    match (options.rows, options.cols) {
+        (Some(rows), Some(cols)) if cells(rows, cols).is_none() => {
+            return Err(SolveError::InvalidDimensions { rows, cols }.into())
+        }
+        // the other side is at least 2, whatever it turns out to be
+        (Some(n), None) | (None, Some(n)) if cells(n, 2).is_none() => {
+            let name = if options.rows.is_some() { "rows" } else { "columns" };
+            return Err(Failure::invalid(format!(
+                "A board with {} {} has more than {} cells.",
+                n,
+                name,
+                Board::MAX_CELLS
+            )));
+        }
+        _ => {}
+    }
+    Ok(options)
+}
+
+fn number(name: &str, value: &str) -> Result<usize, Failure> {
+    value
+        .parse()
+        .map_err(|_| Failure::invalid(format!("{} needs a whole number, got `{}`.", name, value)))
+}
+
+/// Picks an algorithm by name.
+fn algorithm(name: &str) -> Result<Algorithm, Failure> {
+    match name.to_lowercase().as_str() {
+        "astar" | "a*" => Ok(Algorithm::AStar),
+        // the weight is filled in from --weight once every option has been read
+        "weighted" | "wastar" | "wa*" => Ok(Algorithm::WeightedAStar { weight: 1.0 }),
+        "ara" | "arastar" | "ara*" => Ok(Algorithm::AraStar { weight: 1.0 }),
+        "greedy" => Ok(Algorithm::Greedy),
+        // the width is filled in from --width once every option has been read
+        "beam" => Ok(Algorithm::Beam { width: 0 }),
+        "ida" | "idastar" | "ida*" => Ok(Algorithm::IdaStar),
+        "rbfs" => Ok(Algorithm::Rbfs),
+        // the budget is filled in from --max-nodes once every option has been read
+        "sma" | "smastar" | "sma*" => Ok(Algorithm::SmaStar { max_nodes: 0 }),
+        "mm" | "bidirectional" => Ok(Algorithm::Bidirectional),
+        "bfs" | "breadth-first" => Ok(Algorithm::BreadthFirst),
+        // the limit is filled in from --depth once every option has been read
+        "dfs" | "depth-first" => Ok(Algorithm::DepthFirst { limit: 0 }),
+        "iddfs" | "iterative-deepening" => Ok(Algorithm::IterativeDeepening),
+        "ucs" | "uniform-cost" => Ok(Algorithm::UniformCost),
+        _ => Err(Failure::invalid(format!("Unknown algorithm `{}`.", name))),
+    }
+}
+
+/// Picks a heuristic by name. Pattern databases are built, or loaded from `--pdb`, for `goal`.
+fn heuristic(options: &Options, goal: &Goal) -> Result<Box<dyn Heuristic>, Failure> {
+    match options.heuristic.as_str() {
+        "manhattan" => Ok(Box::new(Manhattan)),
+        "hamming" => Ok(Box::new(Hamming)),
+        "linear-conflict" | "linear" => Ok(Box::new(LinearConflict)),
+        "table" => match DistanceTable::build(goal) {
+            Some(table) => Ok(Box::new(table)),
+            None => Err(Failure::invalid(format!(
+                "Only boards of up to {} cells can be tabulated.",
+                DistanceTable::MAX_CELLS
+            ))),
+        },
+        "pdb" => {
+            let sizes = match &options.partition {
+                Some(sizes) => sizes.clone(),
+                None => {
+                    let tiles = goal.tiles().len() - 1;
+                    let mut sizes = vec![5; tiles / 5];
+                    if !tiles.is_multiple_of(5) {
+                        sizes.push(tiles % 5);
+                    }
+                    sizes
+                }
+            };
+            let partitions = PatternDatabase::partition(goal, &sizes);
+            let db = match &options.pdb {
+                Some(path) => PatternDatabase::load_or_build(path, goal, &partitions),
+                None => PatternDatabase::build(goal, &partitions),
+            };
+            db.map(|db| Box::new(db) as Box<dyn Heuristic>)
+                .map_err(|e| Failure {
+                    code: match e {
+                        PatternDbError::Io(_) => IO_ERROR,
+                        _ => INVALID,
+                    },
+                    message: e.to_string(),
+                })
+        }
+        _ => Err(Failure::invalid(format!(
+            "Unknown heuristic `{}`.",
+            options.heuristic
+        ))),
+    }
+}
+
+/// Reads a board written as tiles separated by commas or spaces.
+fn board(text: &str) -> Result<Vec<isize>, Failure> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse()
+                .map_err(|_| Failure::invalid(format!("`{}` isn't a tile.", s)))
+        })
+        .collect()
+}
+
+/// Works out the initial state, goal state and size of the board from the options.
+fn boards(options: &Options) -> Result<(Vec<isize>, Vec<isize>, usize, usize), Failure> {
+    let mut start = options.start.clone();
+    let mut goal = options.goal.clone();
+
+    if let Some(path) = &options.file {
+        let mut text = String::new();
+        let read = if path == "-" {
+            io::stdin().read_to_string(&mut text).map(|_| ())
+        } else {
+            fs::read_to_string(path).map(|t| text = t)
+        };
+        read.map_err(|e| Failure {
+            code: IO_ERROR,
+            message: format!("Couldn't read {}: {}", path, e),
+        })?;
+
+        let mut lines = text.lines().map(str::trim).filter(|l| !l.is_empty());
+        start = start.or_else(|| lines.next().map(String::from));
+        goal = goal.or_else(|| lines.next().map(String::from));
+    }
+
+    let mut positional = options.positional.iter().cloned();
+    let start = start
+        .or_else(|| positional.next())
+        .ok_or_else(|| Failure::invalid("No initial state given. Run with `help` for usage."))?;
+    let goal = goal.or_else(|| positional.next());
+    if let Some(extra) = positional.next() {
+        return Err(Failure::invalid(format!(
+            "Unexpected argument `{}`.",
+            extra
+        )));
+    }
+
+    let start = board(&start)?;
+    let (rows, cols) = dimensions(options, start.len());
+    let goal = match goal {
+        Some(goal) => board(&goal)?,
+        None => default_goal(rows * cols),
+    };
+    Ok((start, goal, rows, cols))
+}
+
+/// Size of the board, from `--rows` and `--cols` or else a square big enough for `len` tiles.
+fn dimensions(options: &Options, len: usize) -> (usize, usize) {
+    let side = (len as f64).sqrt().round() as usize;
+    match (options.rows, options.cols) {
+        (Some(rows), Some(cols)) => (rows, cols),
+        (Some(rows), None) => (rows, len / rows.max(1)),
+        (None, Some(cols)) => (len / cols.max(1), cols),
+        (None, None) => (side, side),
+    }
+}
+
+/// The tiles in order, with the blank last.
+fn default_goal(len: usize) -> Vec<isize> {
+    (1..len as isize).chain(Some(0)).collect()
+}
+
+/// Sets up a problem with the limits and reopening from the options.
+fn problem(
+    options: &Options,
+    start: Vec<isize>,
+    goal: Vec<isize>,
+    rows: usize,
+    cols: usize,
+) -> Result<Problem, Failure> {
+    Ok(Problem::with_dimensions(rows, cols, start, goal)?
+        .with_limits(options.limits)
+        .with_reopening(options.reopen))
+}
+
+/// `solve`: solves one board and prints the solution.
+fn solve(options: &Options) -> Result<i32, Failure> {
+    let (start, goal, rows, cols) = boards(options)?;
+    let mut problem = problem(options, start, goal.clone(), rows, cols)?;
+    let heuristic = heuristic(options, &Goal::new(goal, rows, cols))?;
+
+    if options.format == Format::Human {
+        println!("{:?}", problem);
+        if options.algorithm.is_informed() {
+            println!("Solving using {} distance...", heuristic.name());
+        } else {
+            println!("Solving using {}...", options.algorithm);
+        }
+    }
+    let soln = match options.algorithm {
+        Algorithm::AraStar { weight } => problem.solve_anytime(weight, heuristic, |soln| {
+            if options.format == Format::Human {
+                println!(
+                    "Found {} moves with a weight of {}, at most {:.3} times as long as a shortest solution.",
+                    soln.cost,
+                    soln.stats.bound.unwrap_or(1.0),
+                    soln.stats.ratio.unwrap_or(1.0)
+                );
+            }
+        })?,
+        algorithm => problem.solve_with(algorithm, heuristic)?,
+    };
+    match options.format {
+        Format::Human => {
+            println!("{}", soln);
+            if let Some(notation) = options.notation {
+                println!("Moves: {}", notation.write_all(&soln.moves));
+            }
+            println!(
+                "Solved in {} moves, taking {:?}.",
+                soln.cost, soln.stats.elapsed
+            );
+            if soln.stats.reexpanded > 0 {
+                println!("Expanded {} nodes more than once.", soln.stats.reexpanded);
+            }
+            // a search meant to find a shortest solution only says so when it may not have
+            if let Some(ratio) = soln.stats.ratio.filter(|&r| r > 1.0 || soln.stats.bound != Some(1.0)) {
+                println!("At most {:.3} times as long as a shortest solution.", ratio);
+            }
+        }
+        Format::Json => println!("{}", solution_json(&soln, options.notation)),
+    }
+    Ok(OK)
+}
+
+/// `check`: validates a board and compares its parity with the goal, without searching.
+fn check(options: &Options) -> Result<i32, Failure> {
+    let (start, goal, rows, cols) = boards(options)?;
+    let problem = problem(options, start.clone(), goal.clone(), rows, cols)?;
+    let initial = Parity::of(&start, cols);
+    let target = Parity::of(&goal, cols);
+    let solvable = problem.check_solvable().is_ok();
+
+    match options.format {
+        Format::Human => {
+            println!("{:?}", problem);
+            println!("Initial state: {}\nGoal state:    {}", initial, target);
+            println!(
+                "{}",
+                if solvable {
+                    "Solvable."
+                } else {
+                    "No solution exists."
+                }
+            );
+        }
+        Format::Json => println!(
+            "{{\"status\":{},\"initial\":{},\"goal\":{}}}",
+            json_str(if solvable { "solvable" } else { "unsolvable" }),
+            parity_json(&initial),
+            parity_json(&target)
+        ),
+    }
+    Ok(if solvable { OK } else { UNSOLVABLE })
+}
+
+/// `generate`: prints random boards that can reach the goal.
+fn generate(options: &Options) -> Result<i32, Failure> {
+    let (rows, cols) = random_dimensions(options)?;
+    let mut rng = Rng::new(options.seed);
+    let goal = default_goal(rows * cols);
+
+    for _ in 0..options.count.unwrap_or(1) {
+        let board = rng.board(&goal, cols, options.moves);
+        let text: Vec<String> = board.iter().map(|t| t.to_string()).collect();
+        match options.format {
+            Format::Human => println!("{}", text.join(",")),
+            Format::Json => println!("[{}]", text.join(",")),
+        }
+    }
+    Ok(OK)
+}
+
+/// `bench`: solves random boards and reports averages.
+fn bench(options: &Options) -> Result<i32, Failure> {
+    let (rows, cols) = random_dimensions(options)?;
+    let mut rng = Rng::new(options.seed);
+    let goal = default_goal(rows * cols);
+    let heuristic = std::rc::Rc::new(heuristic(options, &Goal::new(goal.clone(), rows, cols))?);
+    let count = options.count.unwrap_or(10);
+
+    let (mut solved, mut cost, mut expanded, mut generated) = (0, 0, 0, 0);
+    let begin = Instant::now();
+    for _ in 0..count {
+        let board = rng.board(&goal, cols, options.moves);
+        let mut problem = problem(options, board.clone(), goal.clone(), rows, cols)?;
+        let result = problem.solve_with(options.algorithm, heuristic.clone());
+
+        if let Format::Json = options.format {
+            match &result {
+                Ok(soln) => println!("{}", solution_json(soln, options.notation)),
+                Err(e) => println!(
+                    "{{\"status\":{},\"error\":{}}}",
+                    json_str(status(exit_code(e))),
+                    json_str(&e.to_string())
+                ),
+            }
+        }
+        if let Ok(soln) = result {
+            solved += 1;
+            cost += soln.cost;
+            expanded += soln.stats.expanded;
+            generated += soln.stats.generated;
+        }
+    }
+    let elapsed = begin.elapsed();
+
+    let average = |total: usize| total as f64 / solved.max(1) as f64;
+    match options.format {
+        Format::Human => {
+            let using = if options.algorithm.is_informed() {
+                format!("{} distance", heuristic.name())
+            } else {
+                options.algorithm.to_string()
+            };
+            println!(
+                "Solved {} of {} random {}x{} boards using {}.",
+                solved, count, rows, cols, using
+            );
+            println!("Average solution length: {:.1} moves.", average(cost));
+            println!("Average nodes expanded: {:.1}.", average(expanded));
+            println!("Average nodes generated: {:.1}.", average(generated));
+            println!("Total time: {:?}.", elapsed);
+        }
+        Format::Json => println!(
+            "{{\"boards\":{},\"solved\":{},\"average_cost\":{:.3},\"average_expanded\":{:.3},\"average_generated\":{:.3},\"elapsed_ms\":{:.3}}}",
+            count,
+            solved,
+            average(cost),
+            average(expanded),
+            average(generated),
+            elapsed.as_secs_f64() * 1000.0
+        ),
+    }
+    Ok(if solved == count { OK } else { GAVE_UP })
+}
+
+/// Size of the random boards for `generate` and `bench`, 3x3 unless given.
+fn random_dimensions(options: &Options) -> Result<(usize, usize), Failure> {
+    let rows = options.rows.or(options.cols).unwrap_or(3);
+    let cols = options.cols.unwrap_or(rows);
+    match rows.checked_mul(cols) {
+        Some(cells) if rows >= 2 && cols >= 2 && cells <= Board::MAX_CELLS => Ok((rows, cols)),
+        _ => Err(SolveError::InvalidDimensions { rows, cols }.into()),
+    }
+}
+
+/// A solution as a single line of JSON.
+fn solution_json(soln: &Solution, notation: Option<Notation>) -> String {
+    let notation = notation.unwrap_or(Notation::Blank);
+    let moves: Vec<String> = soln
+        .moves
+        .iter()
+        .map(|m| json_str(m.notate(notation)))
+        .collect();
+    let path: Vec<String> = soln
+        .path
+        .iter()
+        .map(|b| {
+            let tiles: Vec<String> = b.iter().map(|t| t.to_string()).collect();
+            format!("[{}]", tiles.join(","))
+        })
+        .collect();
+    let iterations: Vec<String> = soln
+        .stats
+        .iterations
+        .iter()
+        .map(|i| {
+            format!(
+                "{{\"threshold\":{},\"expanded\":{},\"generated\":{}}}",
+                i.threshold, i.expanded, i.generated
+            )
+        })
+        .collect();
+
+    let mut json = String::new();
+    let _ = write!(
+        json,
+        "{{\"status\":\"solved\",\"cost\":{},\"moves\":[{}],\"path\":[{}],\"expanded\":{},\"generated\":{},\"duplicates\":{},\"stale\":{},\"reopened\":{},\"reexpanded\":{},\"bound\":{},\"ratio\":{},\"elapsed_ms\":{:.3},\"iterations\":[{}]}}",
+        soln.cost,
+        moves.join(","),
+        path.join(","),
+        soln.stats.expanded,
+        soln.stats.generated,
+        soln.stats.duplicates,
+        soln.stats.stale,
+        soln.stats.reopened,
+        soln.stats.reexpanded,
+        json_number(soln.stats.bound),
+        json_number(soln.stats.ratio),
+        soln.stats.elapsed.as_secs_f64() * 1000.0,
+        iterations.join(",")
+    );
+    json
+}
+
+/// A number, or `null` if there isn't one.
+fn json_number(n: Option<f64>) -> String {
+    match n {
+        Some(n) => n.to_string(),
+        None => String::from("null"),
+    }
+}
+
+fn parity_json(p: &Parity) -> String {
+    format!(
+        "{{\"inversions\":{},\"blank_row\":{},\"odd\":{}}}",
+        p.inversions, p.blank_row, p.odd
+    )
+}
+
+/// Quotes a string for JSON.
+fn json_str(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// # A small xorshift random number generator, so boards can be reproduced from a seed.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: Option<u64>) -> Rng {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or(0)
+        });
+        // the state must never be zero
+        Rng(seed ^ 0x9E37_79B9_7F4A_7C15)
+    }
+
+    fn next(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// A number in `0..n`.
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    /// A random board that can reach `goal`. Either `moves` random moves away from it, or shuffled completely.
+    fn board(&mut self, goal: &[isize], cols: usize, moves: Option<usize>) -> Vec<isize> {
+        let mut board = goal.to_vec();
+        match moves {
+            Some(moves) => {
+                let mut blank = board.iter().position(|&t| t == 0).unwrap_or(0);
+                let mut last: Option<Move> = None;
+                for _ in 0..moves {
+                    let options: Vec<(usize, Move)> = board::neighbours(blank, cols, board.len())
+                        .iter()
+                        .flatten()
+                        .cloned()
+                        .filter(|&(_, m)| Some(m.inverse()) != last)
+                        .collect();
+                    let (next, m) = options[self.below(options.len())];
+                    board.swap(blank, next);
+                    last = Some(m);
+                    blank = next;
+                }
+            }
+            None => {
+                for i in (1..board.len()).rev() {
+                    let j = self.below(i + 1);
+                    board.swap(i, j);
+                }
+                if Parity::of(&board, cols).odd != Parity::of(goal, cols).odd {
+                    // swapping two tiles flips the parity without moving the blank
+                    let tiles: Vec<usize> = (0..board.len())
+                        .filter(|&i| board[i] != 0)
+                        .take(2)
+                        .collect();
+                    board.swap(tiles[0], tiles[1]);
+                }
+            }
+        }
+        board
+    }
+}
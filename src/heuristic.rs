@@ -0,0 +1,394 @@
+//! # Heuristics
+//! Functions that estimate how many moves a board is from the goal state.
+//! A* uses these to decide which state to expand next.
+//! Implement the `Heuristic` trait to plug in your own, or pick one of the built-in ones -
+//!- `Manhattan`:   Sum of the distances of every tile from where it belongs.
+//!- `Hamming`:     Number of tiles that aren't where they belong.
+//!- `LinearConflict`: Manhattan distance, plus two moves for every tile that has to make way for another.
+//!- `DistanceTable`: The exact number of moves, looked up in a table of every board. Small boards only.
+//!
+//! None of them count the blank, which moves with every move, so none of them overestimate the number of
+//! moves left. `check_admissible` verifies that for any heuristic on boards small enough to enumerate.
+use crate::board::{neighbours, Board};
+use crate::permutation::{factorial, rank_reachable};
+use std::collections::{HashSet, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::rc::Rc;
+
+/// # The goal state, along with what heuristics need to know about it.
+/// ## Properties
+///- `tiles`:       The goal board, row by row.
+///- `positions`:   Index of every tile on the goal board, i.e. `tiles[positions[t]] == t`.
+///- `rows`:        Number of rows on the board.
+///- `cols`:        Number of columns on the board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Goal {
+    tiles: Vec<isize>,
+    positions: Vec<usize>,
+    rows: usize,
+    cols: usize,
+}
+
+impl Goal {
+    /// Takes a goal board holding one of each tile from `0` to `rows * cols - 1`.
+    pub fn new(tiles: Vec<isize>, rows: usize, cols: usize) -> Goal {
+        let mut positions = vec![0; tiles.len()];
+        for (i, &t) in tiles.iter().enumerate() {
+            positions[t as usize] = i;
+        }
+        Goal {
+            tiles,
+            positions,
+            rows,
+            cols,
+        }
+    }
+
+    /// The goal board, row by row.
+    pub fn tiles(&self) -> &[isize] {
+        &self.tiles
+    }
+
+    /// Number of rows on the board.
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Number of columns on the board.
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// Index of `tile` on the goal board.
+    pub fn position(&self, tile: isize) -> usize {
+        self.positions[tile as usize]
+    }
+}
+
+/// # Estimates the number of moves needed to take a board to the goal state.
+/// Implementations must never return a negative number.
+pub trait Heuristic {
+    /// Name used when printing, like "Manhattan".
+    fn name(&self) -> &str;
+
+    /// Estimates the number of moves needed to take `board` to `goal`.
+    fn estimate(&self, board: &[isize], goal: &Goal) -> isize;
+
+    /// Whether `estimate` works for `goal`. Heuristics that are built for one goal, like lookup tables, only
+    /// work for that one, which matters to searches that also head back towards the initial state.
+    fn supports(&self, _goal: &Goal) -> bool {
+        true
+    }
+
+    /// Whether `estimate` never drops by more than one from a board to the next. If it doesn't, A* never finds
+    /// a cheaper path to a board it has expanded, and weighted A* keeps within its weight without reopening
+    /// boards. Implementations that aren't sure should leave this `false`.
+    fn is_consistent(&self) -> bool {
+        false
+    }
+}
+
+impl<H: Heuristic + ?Sized> Heuristic for Box<H> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn estimate(&self, board: &[isize], goal: &Goal) -> isize {
+        (**self).estimate(board, goal)
+    }
+
+    fn supports(&self, goal: &Goal) -> bool {
+        (**self).supports(goal)
+    }
+
+    fn is_consistent(&self) -> bool {
+        (**self).is_consistent()
+    }
+}
+
+/// Lets a single heuristic, like a large lookup table, be shared by many problems.
+impl<H: Heuristic + ?Sized> Heuristic for Rc<H> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn estimate(&self, board: &[isize], goal: &Goal) -> isize {
+        (**self).estimate(board, goal)
+    }
+
+    fn supports(&self, goal: &Goal) -> bool {
+        (**self).supports(goal)
+    }
+
+    fn is_consistent(&self) -> bool {
+        (**self).is_consistent()
+    }
+}
+
+/// Sum of the horizontal and vertical distances of every tile from where it belongs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Manhattan;
+
+impl Heuristic for Manhattan {
+    fn name(&self) -> &str {
+        "Manhattan"
+    }
+
+    fn estimate(&self, board: &[isize], goal: &Goal) -> isize {
+        let cols = goal.cols();
+        let mut cost = 0;
+        for (i, &t) in board.iter().enumerate() {
+            if t != 0 {
+                let g = goal.position(t);
+                cost += (i / cols).abs_diff(g / cols) + (i % cols).abs_diff(g % cols);
+            }
+        }
+        cost as isize
+    }
+
+    // a move changes the distance of one tile by one
+    fn is_consistent(&self) -> bool {
+        true
+    }
+}
+
+/// Number of tiles that aren't where they belong.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Hamming;
+
+impl Heuristic for Hamming {
+    fn name(&self) -> &str {
+        "Hamming"
+    }
+
+    fn estimate(&self, board: &[isize], goal: &Goal) -> isize {
+        board
+            .iter()
+            .zip(goal.tiles())
+            .filter(|&(&t, &g)| t != 0 && t != g)
+            .count() as isize
+    }
+
+    // a move puts one tile in or out of place
+    fn is_consistent(&self) -> bool {
+        true
+    }
+}
+
+/// # Manhattan distance plus linear conflicts.
+/// Two tiles are in linear conflict when they are both in the row (or column) they belong in, but in the
+/// wrong order. One of them has to step out of the line and back in to let the other pass, which costs two
+/// moves on top of their Manhattan distance.
+/// ## Further details
+/// For every row, the smallest number of tiles that have to leave it is the number of tiles in their goal
+/// row minus the longest run of them (not necessarily adjacent) already in goal order.
+/// Each of those tiles adds two moves. Columns are handled the same way.
+/// The blank isn't counted, since it's the one piece that moves with every move, so the estimate never
+/// exceeds the true distance.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LinearConflict;
+
+impl Heuristic for LinearConflict {
+    fn name(&self) -> &str {
+        "Linear Conflict"
+    }
+
+    fn estimate(&self, board: &[isize], goal: &Goal) -> isize {
+        let (rows, cols) = (goal.rows(), goal.cols());
+        let mut cost = 0;
+        for (i, &t) in board.iter().enumerate() {
+            if t != 0 {
+                let g = goal.position(t);
+                cost += (i / cols).abs_diff(g / cols) + (i % cols).abs_diff(g % cols);
+            }
+        }
+
+        let mut line = Vec::with_capacity(rows.max(cols));
+        for r in 0..rows {
+            line.clear();
+            for c in 0..cols {
+                let t = board[r * cols + c];
+                let g = goal.position(t);
+                if t != 0 && g / cols == r {
+                    line.push(g % cols);
+                }
+            }
+            cost += 2 * (line.len() - longest_increasing(&line));
+        }
+        for c in 0..cols {
+            line.clear();
+            for r in 0..rows {
+                let t = board[r * cols + c];
+                let g = goal.position(t);
+                if t != 0 && g % cols == c {
+                    line.push(g / cols);
+                }
+            }
+            cost += 2 * (line.len() - longest_increasing(&line));
+        }
+        cost as isize
+    }
+
+    // a move changes one tile's Manhattan distance by one, and the conflicts of the one line it leaves or joins
+    fn is_consistent(&self) -> bool {
+        true
+    }
+}
+
+/// Length of the longest strictly increasing subsequence of `xs`.
+fn longest_increasing(xs: &[usize]) -> usize {
+    // tails[k] is the smallest value that ends an increasing subsequence of length k + 1
+    let mut tails: Vec<usize> = Vec::with_capacity(xs.len());
+    for &x in xs {
+        match tails.binary_search(&x) {
+            Ok(_) => (),
+            Err(k) if k == tails.len() => tails.push(x),
+            Err(k) => tails[k] = x,
+        }
+    }
+    tails.len()
+}
+
+/// # The exact number of moves from every board to the goal.
+/// Boards are numbered by `permutation::rank_reachable`, and the table holds one byte per board, filled by
+/// a breadth first search outwards from the goal. That's 181,440 bytes for the 8-puzzle, and A* with it
+/// only ever expands the states on a shortest path.
+/// ## Properties
+///- `goal`:        The goal state the table was built for.
+///- `distances`:   Number of moves from each board to the goal, indexed by its rank.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistanceTable {
+    goal: Goal,
+    distances: Vec<u8>,
+}
+
+impl DistanceTable {
+    /// Largest board that can be tabulated. The 2x5 puzzle has 1,814,400 boards.
+    pub const MAX_CELLS: usize = 10;
+
+    /// Tabulates every board that can reach `goal`, if it has no more than `MAX_CELLS` cells.
+    pub fn build(goal: &Goal) -> Option<DistanceTable> {
+        let n = goal.tiles().len();
+        if n > DistanceTable::MAX_CELLS {
+            return None;
+        }
+        let mut distances = vec![u8::MAX; factorial(n)? / 2];
+        let mut queue = VecDeque::new();
+        let start = Board::pack(goal.tiles())?;
+        distances[rank_reachable(goal.tiles())] = 0;
+        queue.push_back((start, goal.position(0)));
+
+        let mut tiles = vec![0; n];
+        while let Some((board, blank)) = queue.pop_front() {
+            board.unpack_into(&mut tiles);
+            let d = distances[rank_reachable(&tiles)];
+            for &(next, _) in neighbours(blank, goal.cols(), n).iter().flatten() {
+                tiles.swap(blank, next);
+                let i = rank_reachable(&tiles);
+                tiles.swap(blank, next);
+                if distances[i] == u8::MAX {
+                    distances[i] = d + 1;
+                    let mut child = board;
+                    child.swap(blank, next);
+                    queue.push_back((child, next));
+                }
+            }
+        }
+
+        Some(DistanceTable {
+            goal: goal.clone(),
+            distances,
+        })
+    }
+
+    /// The goal state the table was built for.
+    pub fn goal(&self) -> &Goal {
+        &self.goal
+    }
+
+    /// Number of moves from `board` to the goal. Only meaningful for boards that can reach it.
+    pub fn distance(&self, board: &[isize]) -> usize {
+        self.distances[rank_reachable(board)] as usize
+    }
+}
+
+impl Heuristic for DistanceTable {
+    fn name(&self) -> &str {
+        "Distance Table"
+    }
+
+    fn estimate(&self, board: &[isize], goal: &Goal) -> isize {
+        debug_assert_eq!(*goal, self.goal, "distance table built for another goal");
+        self.distance(board) as isize
+    }
+
+    fn supports(&self, goal: &Goal) -> bool {
+        *goal == self.goal
+    }
+
+    // exact distances change by exactly one with every move
+    fn is_consistent(&self) -> bool {
+        true
+    }
+}
+
+/// # A board the heuristic estimated to be further from the goal than it really is.
+/// ## Properties
+///- `board`:       The board, row by row.
+///- `estimate`:    What the heuristic estimated.
+///- `distance`:    The true number of moves from `board` to the goal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overestimate {
+    pub board: Vec<isize>,
+    pub estimate: isize,
+    pub distance: usize,
+}
+
+impl fmt::Display for Overestimate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Estimated {} moves for {:?}, which is {} moves from the goal.",
+            self.estimate, self.board, self.distance
+        )
+    }
+}
+
+impl Error for Overestimate {}
+
+/// # Checks that a heuristic never overestimates the number of moves to the goal.
+/// Runs a breadth first search outwards from the goal, which finds the true distance of every board that
+/// can reach it, and compares each with the heuristic's estimate.
+/// Returns the number of boards checked, or the closest board to the goal that was overestimated.
+/// ## Further details
+/// Half of all the ways to arrange the tiles can reach the goal, and every one of them is kept in memory,
+/// so this is only practical up to the 3x3 and 2x4 puzzles (181,440 and 20,160 boards).
+pub fn check_admissible<H: Heuristic + ?Sized>(heuristic: &H, goal: &Goal) -> Result<usize, Overestimate> {
+    let start = goal.tiles().to_vec();
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    seen.insert(start.clone());
+    queue.push_back((start, 0));
+
+    while let Some((board, distance)) = queue.pop_front() {
+        let estimate = heuristic.estimate(&board, goal);
+        if estimate > distance as isize {
+            return Err(Overestimate {
+                board,
+                estimate,
+                distance,
+            });
+        }
+
+        let blank = board.iter().position(|&t| t == 0).unwrap_or(0);
+        for &(next, _) in neighbours(blank, goal.cols(), board.len()).iter().flatten() {
+            let mut child = board.clone();
+            child.swap(blank, next);
+            if seen.insert(child.clone()) {
+                queue.push_back((child, distance + 1));
+            }
+        }
+    }
+    Ok(seen.len())
+}
@@ -0,0 +1,1194 @@
+#![doc(html_root_url = "https://docs.rs/crate/project_1_itcs_6156/0.1.3")]
+//! # 8 Puzzle Problem
+//! ## Introduction
+//! The 8-puzzle problem is a puzzle invented and popularized by Noyes Palmer Chapman in the 1870s.
+//! It is played on a 3-by-3 grid with 8 square blocks labeled 1 through 8 and a blank square.
+//! Your goal is to rearrange the blocks so that they are in order.
+//! You are permitted to slide blocks horizontally or vertically into the blank square.
+//! The following shows a sequence of legal moves from an initial board position (left) to the goal position (right).
+//! ```text
+//!    1  3        1     3        1  2  3        1  2  3        1  2  3
+//! 4  2  5   =>   4  2  5   =>   4     5   =>   4  5      =>   4  5  6
+//! 7  8  6        7  8  6        7  8  6        7  8  6        7  8
+//! initial                                                      goal
+//! ```
+//! ## How to install and run
+//! First install `rustup` from [here](https://rustup.rs/). `rustup` is the toolchain for a language called `rust`, which this project is written it. //! Installing it will allow you to run my program.
+//! > If you don't want to install it, I've also included a Windows executable that can be run without any dependencies. Look for `project_1.exe` in //! the root folder.
+//!
+//! Once `rustup` is installed, execute the following commands to run the code -
+//! ```bash
+//! git clone https://github.com/DhruvDh/project_1_itcs_6150.git
+//! cd project_1_itcs_6156 && cargo run
+//! ```
+//! That solves the examples further down this page. To solve a board of your own, pass it to the `solve` command, row by row, with `0` for the blank. The goal defaults to the tiles in order with the blank last -
+//! ```bash
+//! cargo run -- solve 1,2,3,7,4,5,6,8,0 1,2,3,8,6,4,7,5,0
+//! cargo run -- solve "8 6 7 2 5 4 3 0 1" --heuristic linear-conflict --algorithm ida
+//! cargo run -- solve --file boards.txt --format json   # first line is the initial state, second is the goal
+//! cargo run -- check 0,3,1,4,2,5,7,8,6                 # is it solvable?
+//! cargo run -- generate --rows 4 --count 5 --seed 1    # random solvable 15-puzzles
+//! cargo run -- bench --count 100 --heuristic pdb       # average cost and nodes over random boards
+//! cargo run -- bench --count 100 --moves 12 --algorithm bfs   # the same, without a heuristic
+//! ```
+//! Run `cargo run -- help` for every option. The exit code is `0` when the board was solved, `1` when it can't be solved, `2` for invalid input, `3` when the search gave up because of `--max-expanded`, `--timeout` or `--max-nodes`, and `4` when a file couldn't be read.
+//! To change the examples, find `fn demo()` inside `src/main.rs` and edit the list of initial and goal states. Change the cost function by changing the last argument of `run`, which is passed on to `Problem::solve`.
+//! ```rust,ignore
+//! run(init_state, goal_state, Hamming); // or Manhattan, or your own implementation of the Heuristic trait.
+//! ```
+//! ## Problem Formulation
+//! - **Goal**: Pieces end up in locations as described by the goal state.
+//! - **States**: All possible permutations of the puzzle.
+//! - **Actions**: Move the blank Up, Down, Left, or Right.
+//! - **Performance Measure**: Number of total moves in the solution, if the solution exists.
+//! ## Problem Structure
+//! The crate is a library, with a small binary built on top of it. The library is composed of 2 major structs, `Problem` and `State`, and the following modules -
+//! - `board`: Where the blank can move, whether a goal can be reached (`Parity`), and printing boards (`Grid`).
+//! - `heuristic`: The `Heuristic` trait and the built-in heuristics.
+//! - `pattern_db`: Additive pattern databases, and saving them to disk.
+//! - `search`: The search algorithms `Problem::solve_with` can run.
+//!
+//! The problem state exposes a `solve` method, which is all that is needed to find a solution.
+//! The binary (`src/main.rs` and `src/cli.rs`) only uses the library's public API, so other programs can depend on the crate the same way.
+//! More detailed information can be found by clicking on the respective struct's name further down this page.
+//! ## Global Variables
+//! There are no global variables in this implementation.
+//! ## Examples
+//! ### Case 1:
+//! ```text
+//! Current State:             Goal State:
+//! -------------             -------------
+//! | 1 | 2 | 3 |             | 1 | 2 | 3 |
+//! | 7 | 4 | 5 |             | 8 | 6 | 4 |
+//! | 6 | 8 | 0 |             | 7 | 5 | 0 |
+//! -------------             -------------
+//! Solving using Manhattan distance...
+//! Expanded 9 nodes.
+//! Generated 18 nodes.
+//! Solution is ["Up", "Left", "Down", "Left", "Up", "Right", "Down", "Right"]
+//! Current State:             Goal State:
+//! -------------             -------------
+//! | 1 | 2 | 3 |             | 1 | 2 | 3 |
+//! | 7 | 4 | 5 |             | 8 | 6 | 4 |
+//! | 6 | 8 | 0 |             | 7 | 5 | 0 |
+//! -------------             -------------
+//! Solving using Hamming distance...
+//! Expanded 16 nodes.
+//! Generated 33 nodes.
+//! Solution is ["Up", "Left", "Down", "Left", "Up", "Right", "Down", "Right"]
+//! Current State:             Goal State:
+//! -------------             -------------
+//! | 1 | 2 | 3 |             | 1 | 2 | 3 |
+//! | 7 | 4 | 5 |             | 8 | 6 | 4 |
+//! | 6 | 8 | 0 |             | 7 | 5 | 0 |
+//! -------------             -------------
+//! Solving using Linear Conflict distance...
+//! Expanded 9 nodes.
+//! Generated 18 nodes.
+//! Solution is ["Up", "Left", "Down", "Left", "Up", "Right", "Down", "Right"]
+//! Linear conflict expanded as many nodes as Manhattan distance.
+//! ```
+//! ### Case 2:
+//! ```text
+//! Current State:             Goal State:
+//! -------------             -------------
+//! | 2 | 8 | 1 |             | 3 | 2 | 1 |
+//! | 3 | 4 | 6 |             | 8 | 0 | 4 |
+//! | 7 | 5 | 0 |             | 7 | 5 | 6 |
+//! -------------             -------------
+//! Solving using Manhattan distance...
+//! Expanded 6 nodes.
+//! Generated 12 nodes.
+//! Solution is ["Up", "Left", "Up", "Left", "Down", "Right"]
+//! Current State:             Goal State:
+//! -------------             -------------
+//! | 2 | 8 | 1 |             | 3 | 2 | 1 |
+//! | 3 | 4 | 6 |             | 8 | 0 | 4 |
+//! | 7 | 5 | 0 |             | 7 | 5 | 6 |
+//! -------------             -------------
+//! Solving using Hamming distance...
+//! Expanded 7 nodes.
+//! Generated 14 nodes.
+//! Solution is ["Up", "Left", "Up", "Left", "Down", "Right"]
+//! Current State:             Goal State:
+//! -------------             -------------
+//! | 2 | 8 | 1 |             | 3 | 2 | 1 |
+//! | 3 | 4 | 6 |             | 8 | 0 | 4 |
+//! | 7 | 5 | 0 |             | 7 | 5 | 6 |
+//! -------------             -------------
+//! Solving using Linear Conflict distance...
+//! Expanded 6 nodes.
+//! Generated 12 nodes.
+//! Solution is ["Up", "Left", "Up", "Left", "Down", "Right"]
+//! Linear conflict expanded as many nodes as Manhattan distance.
+//! ```
+//! ### Case 3:
+//! ```text
+//! Current State:             Goal State:
+//! -------------             -------------
+//! | 0 | 1 | 3 |             | 1 | 2 | 3 |
+//! | 4 | 2 | 5 |             | 4 | 5 | 6 |
+//! | 7 | 8 | 6 |             | 7 | 8 | 0 |
+//! -------------             -------------
+//! Solving using Manhattan distance...
+//! Expanded 4 nodes.
+//! Generated 9 nodes.
+//! Solution is ["Right", "Down", "Right", "Down"]
+//! Current State:             Goal State:
+//! -------------             -------------
+//! | 0 | 1 | 3 |             | 1 | 2 | 3 |
+//! | 4 | 2 | 5 |             | 4 | 5 | 6 |
+//! | 7 | 8 | 6 |             | 7 | 8 | 0 |
+//! -------------             -------------
+//! Solving using Hamming distance...
+//! Expanded 4 nodes.
+//! Generated 9 nodes.
+//! Solution is ["Right", "Down", "Right", "Down"]
+//! Current State:             Goal State:
+//! -------------             -------------
+//! | 0 | 1 | 3 |             | 1 | 2 | 3 |
+//! | 4 | 2 | 5 |             | 4 | 5 | 6 |
+//! | 7 | 8 | 6 |             | 7 | 8 | 0 |
+//! -------------             -------------
+//! Solving using Linear Conflict distance...
+//! Expanded 4 nodes.
+//! Generated 9 nodes.
+//! Solution is ["Right", "Down", "Right", "Down"]
+//! Linear conflict expanded as many nodes as Manhattan distance.
+//! ```
+//! ### Case 4: (No solution)
+//! ```text
+//! Current State:             Goal State:
+//! -------------             -------------
+//! | 0 | 3 | 1 |             | 1 | 2 | 3 |
+//! | 4 | 2 | 5 |             | 4 | 5 | 6 |
+//! | 7 | 8 | 6 |             | 7 | 8 | 0 |
+//! -------------             -------------
+//! Solving using Manhattan distance...
+//! No solution exists.
+//! Initial state: 5 inversions, blank on row 0 (odd)
+//! Goal state:    0 inversions, blank on row 2 (even)
+//! Current State:             Goal State:
+//! -------------             -------------
+//! | 0 | 3 | 1 |             | 1 | 2 | 3 |
+//! | 4 | 2 | 5 |             | 4 | 5 | 6 |
+//! | 7 | 8 | 6 |             | 7 | 8 | 0 |
+//! -------------             -------------
+//! Solving using Hamming distance...
+//! No solution exists.
+//! Initial state: 5 inversions, blank on row 0 (odd)
+//! Goal state:    0 inversions, blank on row 2 (even)
+//! Current State:             Goal State:
+//! -------------             -------------
+//! | 0 | 3 | 1 |             | 1 | 2 | 3 |
+//! | 4 | 2 | 5 |             | 4 | 5 | 6 |
+//! | 7 | 8 | 6 |             | 7 | 8 | 0 |
+//! -------------             -------------
+//! Solving using Linear Conflict distance...
+//! No solution exists.
+//! Initial state: 5 inversions, blank on row 0 (odd)
+//! Goal state:    0 inversions, blank on row 2 (even)
+//! ```
+
+pub mod board;
+pub mod heuristic;
+pub mod pattern_db;
+pub mod permutation;
+pub mod search;
+
+pub use board::{find, Board, Grid, Move, Notation, Parity, ParseMoveError};
+pub use heuristic::{
+    check_admissible, DistanceTable, Goal, Hamming, Heuristic, LinearConflict, Manhattan, Overestimate,
+};
+pub use pattern_db::{PatternDatabase, PatternDbError};
+pub use search::{Algorithm, Iteration};
+use board::neighbours;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::time::{Duration, Instant};
+/// # A struct to encapsulate State information.
+/// States live in an arena (`Problem::nodes`) and refer to their parent by its index there, so a state is a
+/// fixed 32 bytes with no reference counting, and tracing a solution back only follows indices.
+/// ## Properties
+///- `is`:      The board, packed into a single integer.
+///- `g`:       Cost to get here from the root.
+///- `parent`:  Index of the parent state in the arena. The root, at index 0, is its own parent.
+///- `kind`:    The move that reached this state from the parent, or `None` for the root.
+///- `blank`:   Index of the blank in `is`.
+///- `closed`:  Whether the state's board has been expanded.
+#[derive(Clone, Copy)]
+pub struct State {
+    is: Board,
+    g: isize,
+    parent: u32,
+    kind: Option<Move>,
+    blank: u8,
+    closed: bool,
+}
+
+/// # A state under consideration, ordered so the BinaryHeap pops the best one first.
+/// ## Properties
+///- `cost`:    Cost of the state (g() + h())
+///- `rank`:    Tie-breaking keys, compared in order when two states have the same cost. Lower wins.
+///- `node`:    Index of the state in the arena.
+/// ## Further details
+/// I am using Binary heap as a priority queue to choose the next state to move to.
+/// The priority here is the "cost" in reverse order. To implement this I need to implement ordering trait for Candidate.
+/// This is done via implementing 4 traits - `Ord`, `PartialOrd`, `Eq` (or equality), `PartialEq`.
+/// The ordering trait is implemented such that a Candidate with a lower cost is considered greater than one with a higher cost.
+/// Ties are broken by `rank`, which is worked out from the problem's `TieBreaking` when the state is generated.
+/// The last key is always the order in which states were generated, so no two candidates compare equal.
+#[derive(Debug, Clone, Copy)]
+struct Candidate {
+    cost: isize,
+    rank: (isize, isize),
+    node: u32,
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Candidate) -> Ordering {
+        (other.cost, other.rank).cmp(&(self.cost, self.rank))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Candidate) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Candidate) -> bool {
+        self.cost == other.cost && self.rank == other.rank
+    }
+}
+impl Eq for Candidate {}
+
+/// # What A* orders the states under consideration by.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Priority {
+    /// `g + h`, for A*.
+    Sum,
+    /// `g + w * h`, for weighted A*.
+    Weighted(f64),
+    /// `h` alone, for greedy best first search.
+    Greedy,
+}
+
+impl Priority {
+    /// Weighted costs are kept in fixed point, to this many parts per move, so that candidates still compare
+    /// whole numbers.
+    const SCALE: isize = 1024;
+
+    /// A weight large enough that `w * h` doesn't fit is held at `isize::MAX`, leaving states with any `h` at all
+    /// to the tie-breaking, which prefers the lower `h` by default.
+    fn cost(self, g: isize, h: isize) -> isize {
+        match self {
+            Priority::Sum => g + h,
+            Priority::Weighted(w) => {
+                (g * Priority::SCALE).saturating_add((w * (h * Priority::SCALE) as f64).round() as isize)
+            }
+            Priority::Greedy => h,
+        }
+    }
+}
+
+/// # Verdict for a problem whose goal state cannot be reached from its initial state.
+/// ## Properties
+///- `initial`: Parity of the initial state.
+///- `goal`:    Parity of the goal state, which differs from that of the initial state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unsolvable {
+    pub initial: Parity,
+    pub goal: Parity,
+}
+
+impl fmt::Display for Unsolvable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "No solution exists.\nInitial state: {}\nGoal state:    {}",
+            self.initial, self.goal
+        )
+    }
+}
+
+/// Which of the two boards given to `Problem::new` an error refers to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    Initial,
+    Goal,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Role::Initial => write!(f, "initial state"),
+            Role::Goal => write!(f, "goal state"),
+        }
+    }
+}
+
+/// A resource limit placed on a search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    /// Maximum number of nodes to expand.
+    Expanded(usize),
+    /// Maximum wall-clock time to spend searching.
+    Time(Duration),
+    /// Maximum number of moves a depth first search follows, set by `Algorithm::DepthFirst`.
+    Depth(usize),
+    /// Maximum number of states to keep in memory, set by `Algorithm::SmaStar`.
+    Memory(usize),
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Limit::Expanded(n) => write!(f, "{} expanded nodes", n),
+            Limit::Time(t) => write!(f, "{:?}", t),
+            Limit::Depth(n) => write!(f, "{} moves deep", n),
+            Limit::Memory(n) => write!(f, "{} nodes in memory", n),
+        }
+    }
+}
+
+/// Which of two states with the same cost to prefer, before falling back on the order they were generated in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prefer {
+    /// The state furthest from the root, i.e. with the higher `g`.
+    HigherG,
+    /// The state closest to the goal by the heuristic, i.e. with the lower `h`.
+    LowerH,
+}
+
+/// Which of two states generated at different times to prefer, when nothing else tells them apart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Insertion {
+    /// The state generated first.
+    Fifo,
+    /// The state generated last.
+    Lifo,
+}
+
+/// # How A* chooses between states with the same cost.
+/// ## Properties
+///- `prefer`:      Compared first, if set.
+///- `insertion`:   Compared last. Every state is generated at a different time, so this settles every tie.
+/// ## Further details
+/// The default prefers the lower `h`, then the state generated last, which tends to follow one path
+/// towards the goal instead of alternating between equally good ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TieBreaking {
+    pub prefer: Option<Prefer>,
+    pub insertion: Insertion,
+}
+
+impl Default for TieBreaking {
+    fn default() -> TieBreaking {
+        TieBreaking {
+            prefer: Some(Prefer::LowerH),
+            insertion: Insertion::Lifo,
+        }
+    }
+}
+
+impl TieBreaking {
+    /// Works out the tie-breaking keys of a state, which is the `seq`-th state generated.
+    fn rank(&self, g: isize, h: isize, seq: usize) -> (isize, isize) {
+        let first = match self.prefer {
+            Some(Prefer::HigherG) => -g,
+            Some(Prefer::LowerH) => h,
+            None => 0,
+        };
+        let last = match self.insertion {
+            Insertion::Fifo => seq as isize,
+            Insertion::Lifo => -(seq as isize),
+        };
+        (first, last)
+    }
+}
+
+/// # Resource limits for `Problem::solve`.
+/// ## Properties
+///- `max_expanded`:    Give up after expanding this many nodes.
+///- `max_time`:        Give up after searching for this long.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    pub max_expanded: Option<usize>,
+    pub max_time: Option<Duration>,
+}
+
+/// # Everything that can go wrong when setting up or solving a problem.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SolveError {
+    /// The board is less than 2 cells tall or wide, or has more than `Board::MAX_CELLS` cells.
+    InvalidDimensions { rows: usize, cols: usize },
+    /// A board doesn't have as many tiles as the puzzle has cells.
+    WrongLength {
+        role: Role,
+        expected: usize,
+        found: usize,
+    },
+    /// A board has no blank (`0`) tile.
+    MissingBlank { role: Role },
+    /// A tile appears more than once on a board.
+    DuplicateTile { role: Role, tile: isize },
+    /// A tile appears on one board but not the other.
+    MismatchedTiles { tile: isize, missing_from: Role },
+    /// A tile is negative or too large for the size of the board.
+    OutOfRange { role: Role, tile: isize },
+    /// The goal state cannot be reached from the initial state.
+    Unsolvable(Unsolvable),
+    /// The weight given to `Algorithm::WeightedAStar` or `Algorithm::AraStar` is less than 1, or isn't a finite
+    /// number.
+    InvalidWeight(f64),
+    /// The width given to `Algorithm::Beam` is 0.
+    InvalidWidth,
+    /// The memory budget given to `Algorithm::SmaStar` is 0.
+    InvalidBudget,
+    /// The heuristic only works for another goal, like a `PatternDatabase` built for a different board.
+    UnsupportedGoal { heuristic: String },
+    /// The search ran into one of the limits set with `Problem::with_limits`, the depth limit of
+    /// `Algorithm::DepthFirst`, or the memory budget of `Algorithm::SmaStar`.
+    LimitExceeded { limit: Limit, stats: Box<Stats> },
+    /// The search ran out of states to consider without reaching the goal state.
+    Exhausted(Box<Stats>),
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SolveError::InvalidDimensions { rows, cols } => write!(
+                f,
+                "A {}x{} board isn't supported, it needs at least 2 rows and 2 columns, and at most {} cells.",
+                rows,
+                cols,
+                Board::MAX_CELLS
+            ),
+            SolveError::WrongLength {
+                role,
+                expected,
+                found,
+            } => write!(f, "The {} has {} tiles, expected {}.", role, found, expected),
+            SolveError::MissingBlank { role } => write!(f, "The {} has no blank (0).", role),
+            SolveError::DuplicateTile { role, tile } => {
+                write!(f, "The {} has more than one {}.", role, tile)
+            }
+            SolveError::MismatchedTiles { tile, missing_from } => {
+                write!(f, "The {} has no {}.", missing_from, tile)
+            }
+            SolveError::OutOfRange { role, tile } => {
+                write!(f, "The {} has an out of range tile {}.", role, tile)
+            }
+            SolveError::Unsolvable(u) => write!(f, "{}", u),
+            SolveError::InvalidWeight(w) => {
+                write!(f, "A weight of {} isn't supported, it needs to be at least 1.", w)
+            }
+            SolveError::InvalidWidth => write!(f, "A beam width of 0 isn't supported, it needs to be at least 1."),
+            SolveError::InvalidBudget => {
+                write!(f, "A memory budget of 0 nodes isn't supported, it needs to be at least 1.")
+            }
+            SolveError::UnsupportedGoal { heuristic } => {
+                write!(f, "The {} heuristic was built for another goal.", heuristic)
+            }
+            SolveError::LimitExceeded { limit, stats } => write!(
+                f,
+                "Gave up after reaching the limit of {}.\nExpanded {} nodes.\nGenerated {} nodes.",
+                limit, stats.expanded, stats.generated
+            ),
+            SolveError::Exhausted(stats) => write!(
+                f,
+                "Reached a dead end.\nExpanded {} nodes.\nGenerated {} nodes.",
+                stats.expanded, stats.generated
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+impl From<Unsolvable> for SolveError {
+    fn from(u: Unsolvable) -> SolveError {
+        SolveError::Unsolvable(u)
+    }
+}
+
+/// Adds `node` to the end of an arena, and returns its index.
+fn push_node<T>(nodes: &mut Vec<T>, node: T) -> u32 {
+    // a search runs out of memory long before it generates 2^32 states
+    nodes.push(node);
+    nodes.len() as u32 - 1
+}
+
+/// Checks that a board has one of each tile from `0` to `len - 1`.
+fn validate(board: &[isize], len: usize, role: Role) -> Result<(), SolveError> {
+    if board.len() != len {
+        return Err(SolveError::WrongLength {
+            role,
+            expected: len,
+            found: board.len(),
+        });
+    }
+    if !board.contains(&0) {
+        return Err(SolveError::MissingBlank { role });
+    }
+    let mut seen = HashSet::new();
+    for &tile in board {
+        if !seen.insert(tile) {
+            return Err(SolveError::DuplicateTile { role, tile });
+        }
+    }
+    Ok(())
+}
+
+/// Checks that every tile on either board is also on the other one, and that all of them fit on the board.
+fn validate_pair(init_state: &[isize], goal_state: &[isize]) -> Result<(), SolveError> {
+    for (board, other, missing_from) in [
+        (init_state, goal_state, Role::Goal),
+        (goal_state, init_state, Role::Initial),
+    ] {
+        if let Some(&tile) = board.iter().find(|t| !other.contains(t)) {
+            return Err(SolveError::MismatchedTiles { tile, missing_from });
+        }
+    }
+    let len = init_state.len() as isize;
+    if let Some(&tile) = init_state.iter().find(|&&t| t < 0 || t >= len) {
+        return Err(SolveError::OutOfRange {
+            role: Role::Initial,
+            tile,
+        });
+    }
+    Ok(())
+}
+
+/// # The best state found so far for every board a search has generated.
+/// Small boards are numbered by `permutation::rank_reachable` and looked up in a table of arena indices,
+/// which for the 8-puzzle is 181,440 entries, or about 709 KB. Larger boards are kept in a HashMap.
+/// ## Further details
+/// A board is generated again whenever another path reaches it. The copy with the lowest `g` is the only one
+/// that's kept here, so a copy that was pushed onto the heap before a better one was found is recognised as
+/// stale when it's popped, and thrown away instead of expanded (see `Problem::a_star`).
+enum Seen {
+    Hashed(HashMap<Board, u32>),
+    Ranked { nodes: Vec<u32>, len: usize },
+}
+
+impl Seen {
+    /// Largest board kept in a table, which takes `n! * 2` bytes (7.2 MB for the 2x5 puzzle).
+    const MAX_RANKED_CELLS: usize = 10;
+
+    /// The table holds one more than each index, so that a board that hasn't been generated is `0`, and a new
+    /// table can come straight from zeroed memory instead of being filled in.
+    fn new(len: usize) -> Seen {
+        match permutation::factorial(len) {
+            Some(n) if len <= Seen::MAX_RANKED_CELLS => Seen::Ranked {
+                nodes: vec![0; n / 2],
+                len,
+            },
+            _ => Seen::Hashed(HashMap::new()),
+        }
+    }
+
+    /// Position of `board` in the table.
+    fn index(board: &Board, len: usize) -> usize {
+        let mut tiles = [0; Board::MAX_CELLS];
+        board.unpack_into(&mut tiles[..len]);
+        permutation::rank_reachable(&tiles[..len])
+    }
+
+    /// Index of the best state with this board in the arena, if it has been generated.
+    fn get(&self, board: &Board) -> Option<u32> {
+        match self {
+            Seen::Hashed(nodes) => nodes.get(board).copied(),
+            Seen::Ranked { nodes, len } => {
+                nodes[Seen::index(board, *len)].checked_sub(1)
+            }
+        }
+    }
+
+    /// Forgets every board, for a new search.
+    fn clear(&mut self) {
+        match self {
+            Seen::Hashed(nodes) => nodes.clear(),
+            Seen::Ranked { nodes, .. } => nodes.fill(0),
+        }
+    }
+
+    /// Records `node` as the best state with this board.
+    fn insert(&mut self, board: Board, node: u32) {
+        match self {
+            Seen::Hashed(nodes) => {
+                nodes.insert(board, node);
+            }
+            Seen::Ranked { nodes, len } => nodes[Seen::index(&board, *len)] = node + 1,
+        }
+    }
+}
+
+/// # A struct to encapsulate information about the current problem.
+/// ## Properties
+///- `state`:               Index of the current state in `nodes`.
+///- `nodes`:               Every state generated so far. Index 0 is the initial state.
+///- `goal`:                The goal state, along with the size of the board.
+///- `goal_board`:          The goal state, packed like every other board.
+///- `seen`:                The best state found so far for every board that has been generated.
+///- `under_consideration`: A BinaryHeap that keeps track of states we'll be choosing the next state from.
+///- `no_generated`:        A counter to keep track of number of nodes generated.
+///- `no_expanded`:         A counter to keep track of number of nodes expanded. 
+///- `no_duplicates`:       A counter to keep track of children dropped because their board was already reached as cheaply.
+///- `no_stale`:            A counter to keep track of states popped after a cheaper copy was found.
+///- `no_reopened`:         A counter to keep track of expanded boards that were reached again more cheaply.
+///- `no_reexpanded`:       A counter to keep track of boards expanded again after already being expanded.
+///- `inconsistent`:        States for expanded boards that were reached again more cheaply, but not reopened.
+///- `heuristic`:           The heuristic used to estimate the distance to the goal state.
+///- `limits`:              Resource limits the search must stay within.
+///- `tie_breaking`:        How to choose between states with the same cost.
+///- `reopen`:              Whether to expand a board again when a cheaper path to it is found.
+///- `priority`:            What the states under consideration are ordered by.
+pub struct Problem {
+    state: u32,
+    nodes: Vec<State>,
+    goal: Goal,
+    goal_board: Board,
+    seen: Seen,
+    under_consideration: BinaryHeap<Candidate>,
+    no_generated: usize,
+    no_expanded: usize,
+    no_duplicates: usize,
+    no_stale: usize,
+    no_reopened: usize,
+    no_reexpanded: usize,
+    inconsistent: Vec<u32>,
+    heuristic: Box<dyn Heuristic>,
+    limits: Limits,
+    tie_breaking: TieBreaking,
+    reopen: bool,
+    priority: Priority,
+}
+
+/// ## Struct Implementations
+/// The following are the methods implemented by the struct Problem.
+impl Problem {
+    /// A constructor that produces instances of struct Problem.
+    /// Takes initial state and goal state as arguements, for a square board just big enough to hold them.
+    /// Returns an error if either board isn't a valid board of that size, or if they don't have the same tiles.
+    pub fn new(init_state: Vec<isize>, goal_state: Vec<isize>) -> Result<Problem, SolveError> {
+        let side = (init_state.len() as f64).sqrt().round() as usize;
+        Problem::with_dimensions(side, side, init_state, goal_state)
+    }
+
+    /// A constructor for boards with `rows` rows and `cols` columns, like the 2x4 or 3x5 puzzles.
+    /// Both boards are laid out row by row.
+    pub fn with_dimensions(
+        rows: usize,
+        cols: usize,
+        init_state: Vec<isize>,
+        goal_state: Vec<isize>,
+    ) -> Result<Problem, SolveError> {
+        let len = match rows.checked_mul(cols) {
+            Some(len) if rows >= 2 && cols >= 2 && len <= Board::MAX_CELLS => len,
+            _ => return Err(SolveError::InvalidDimensions { rows, cols }),
+        };
+        validate(&init_state, len, Role::Initial)?;
+        validate(&goal_state, len, Role::Goal)?;
+        validate_pair(&init_state, &goal_state)?;
+
+        let blank = init_state.iter().position(|&x| x == 0).unwrap_or(0);
+        let is = Board::pack(&init_state).ok_or(SolveError::InvalidDimensions { rows, cols })?;
+        let goal_board = Board::pack(&goal_state).ok_or(SolveError::InvalidDimensions { rows, cols })?;
+        Ok(Problem {
+            state: 0,
+            nodes: vec![State {
+                is,
+                g: 0,
+                parent: 0,
+                kind: None,
+                blank: blank as u8,
+                closed: false,
+            }],
+            goal: Goal::new(goal_state, rows, cols),
+            goal_board,
+            seen: Seen::new(len),
+            under_consideration: BinaryHeap::new(),
+            no_generated: 0,
+            no_expanded: 0,
+            no_duplicates: 0,
+            no_stale: 0,
+            no_reopened: 0,
+            no_reexpanded: 0,
+            inconsistent: Vec::new(),
+            heuristic: Box::new(Manhattan),
+            limits: Limits::default(),
+            tie_breaking: TieBreaking::default(),
+            reopen: false,
+            priority: Priority::Sum,
+        })
+    }
+
+    /// Sets resource limits for the search, after which `solve` gives up.
+    pub fn with_limits(mut self, limits: Limits) -> Problem {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets how A* chooses between states with the same cost, making the order states are expanded in
+    /// the same from one run to the next.
+    pub fn with_tie_breaking(mut self, tie_breaking: TieBreaking) -> Problem {
+        self.tie_breaking = tie_breaking;
+        self
+    }
+
+    /// Sets whether A* expands a board again when it finds a cheaper path to it after expanding it.
+    /// With a consistent heuristic, like Manhattan distance, that never happens. With an inconsistent one,
+    /// like a pattern database, A* may return a longer solution than necessary unless this is set.
+    pub fn with_reopening(mut self, reopen: bool) -> Problem {
+        self.reopen = reopen;
+        self
+    }
+
+    /// Expands the current state of the Problem (self.state), and marks it closed.
+    /// Every child whose board hasn't been reached as cheaply before is added to the arena and to the states
+    /// under consideration. A child whose board has already been expanded is only added if reopening is on.
+    /// Otherwise it's only kept in the arena, so the search can tell how much shorter a solution through it
+    /// could have been.
+    /// Returns the number of states generated.
+    pub fn expand(&mut self) -> usize {
+        let parent = self.state;
+        let State { is, g, blank, .. } = self.nodes[parent as usize];
+        let loc = blank as usize;
+        let cols = self.goal.cols();
+
+        let len = self.goal.tiles().len();
+        let mut tiles = [0; Board::MAX_CELLS];
+        let tiles = &mut tiles[..len];
+
+        let mut generated = 0;
+        self.no_expanded += 1; // incrementing
+        if self.nodes[parent as usize].closed {
+            self.no_reexpanded += 1;
+        }
+        self.nodes[parent as usize].closed = true;
+        let g = g + 1;
+
+        // computes cost for every next state and pushes it to the states under consideration
+        for &(new_loc, kind) in neighbours(loc, cols, len).iter().flatten() {
+            let mut new_state = is;
+            new_state.swap(loc, new_loc);
+
+            let mut state = State {
+                is: new_state,
+                g,
+                parent,
+                kind: Some(kind),
+                blank: new_loc as u8,
+                closed: false,
+            };
+            if let Some(best) = self.seen.get(&new_state) {
+                let best = &self.nodes[best as usize];
+                if best.g <= g {
+                    self.no_duplicates += 1;
+                    continue;
+                }
+                if best.closed && !self.reopen {
+                    self.no_duplicates += 1;
+                    let node = push_node(&mut self.nodes, State { closed: true, ..state });
+                    self.seen.insert(new_state, node);
+                    self.inconsistent.push(node);
+                    continue;
+                }
+                if best.closed {
+                    // the board has been expanded, so expanding this copy is a re-expansion
+                    self.no_reopened += 1;
+                    state.closed = true;
+                }
+            }
+
+            new_state.unpack_into(tiles);
+            let h = self.heuristic.estimate(tiles, &self.goal);
+
+            self.no_generated += 1; // incrementing
+            generated += 1;
+            let node = push_node(&mut self.nodes, state);
+            self.seen.insert(new_state, node);
+            self.under_consideration.push(Candidate {
+                cost: self.priority.cost(g, h),
+                rank: self.tie_breaking.rank(g, h, self.no_generated),
+                node,
+            });
+        }
+
+        generated
+    }
+
+    /// Goes back to the initial state and forgets everything an earlier search found, so that every search
+    /// on the same problem starts from scratch.
+    fn reset(&mut self) {
+        self.state = 0;
+        self.nodes.truncate(1);
+        self.nodes[0].closed = false;
+        self.seen.clear();
+        self.under_consideration.clear();
+        self.inconsistent.clear();
+        self.no_generated = 0;
+        self.no_expanded = 0;
+        self.no_duplicates = 0;
+        self.no_stale = 0;
+        self.no_reopened = 0;
+        self.no_reexpanded = 0;
+    }
+
+    /// The state the search is currently at.
+    fn current(&self) -> &State {
+        &self.nodes[self.state as usize]
+    }
+
+    /// Compares the inversion parity of the initial and goal states.
+    /// Returns the parity of both if the goal can never be reached.
+    pub fn check_solvable(&self) -> Result<(), Unsolvable> {
+        let len = self.goal.tiles().len();
+        let initial = Parity::of(&self.current().is.unpack(len), self.goal.cols());
+        let goal = Parity::of(self.goal.tiles(), self.goal.cols());
+
+        if initial.odd == goal.odd {
+            Ok(())
+        } else {
+            Err(Unsolvable { initial, goal })
+        }
+    }
+
+    /// Solves the problem with A*, using the given heuristic.
+    /// Returns early without searching if the goal state is unreachable,
+    /// and gives up if the search runs into one of the limits set with `with_limits`.
+    pub fn solve(&mut self, heuristic: impl Heuristic + 'static) -> Result<Solution, SolveError> {
+        self.solve_with(Algorithm::AStar, heuristic)
+    }
+
+    /// Solves the problem with the given search algorithm and heuristic.
+    /// The heuristic is ignored by algorithms that don't use one (see `Algorithm::is_informed`).
+    /// Every search starts again from the initial state, so the same problem can be solved with one algorithm
+    /// after another.
+    /// Returns early without searching if the goal state is unreachable,
+    /// and gives up if the search runs into one of the limits set with `with_limits`.
+    pub fn solve_with(
+        &mut self,
+        algorithm: Algorithm,
+        heuristic: impl Heuristic + 'static,
+    ) -> Result<Solution, SolveError> {
+        self.search(algorithm, Box::new(heuristic), &mut |_| {})
+    }
+
+    /// Solves the problem with ARA* (see `Algorithm::AraStar`), starting from the given weight, and calls
+    /// `improved` with every solution that's shorter, or proven closer to the shortest, than the one before.
+    /// Each comes with the weight it was found with in `stats.bound`, and how far from the shortest it was
+    /// proven to be in `stats.ratio`.
+    /// Returns the last of them once it's proven to be a shortest solution. If the search runs into one of the
+    /// limits set with `with_limits`, like a deadline, returns the best found so far instead, and only gives
+    /// up if there isn't one yet.
+    pub fn solve_anytime(
+        &mut self,
+        weight: f64,
+        heuristic: impl Heuristic + 'static,
+        mut improved: impl FnMut(&Solution),
+    ) -> Result<Solution, SolveError> {
+        self.search(Algorithm::AraStar { weight }, Box::new(heuristic), &mut improved)
+    }
+
+    /// Solves the problem with the given search algorithm twice, first with `baseline` and then with `heuristic`,
+    /// to compare how many nodes each expanded, like `LinearConflict` against `Manhattan`.
+    pub fn compare(
+        &mut self,
+        algorithm: Algorithm,
+        baseline: impl Heuristic + 'static,
+        heuristic: impl Heuristic + 'static,
+    ) -> Result<Comparison, SolveError> {
+        Ok(Comparison {
+            baseline: self.solve_with(algorithm, baseline)?,
+            solution: self.solve_with(algorithm, heuristic)?,
+        })
+    }
+
+    /// Sets up and runs a search for `solve_with` and `solve_anytime`.
+    fn search(
+        &mut self,
+        algorithm: Algorithm,
+        heuristic: Box<dyn Heuristic>,
+        improved: &mut dyn FnMut(&Solution),
+    ) -> Result<Solution, SolveError> {
+        self.priority = match algorithm {
+            Algorithm::WeightedAStar { weight } | Algorithm::AraStar { weight }
+                if !weight.is_finite() || weight < 1.0 =>
+            {
+                return Err(SolveError::InvalidWeight(weight));
+            }
+            Algorithm::WeightedAStar { weight } | Algorithm::AraStar { weight } => Priority::Weighted(weight),
+            Algorithm::Beam { width: 0 } => return Err(SolveError::InvalidWidth),
+            Algorithm::SmaStar { max_nodes: 0 } => return Err(SolveError::InvalidBudget),
+            Algorithm::Greedy => Priority::Greedy,
+            _ => Priority::Sum,
+        };
+        if algorithm.is_informed() && !heuristic.supports(&self.goal) {
+            return Err(SolveError::UnsupportedGoal {
+                heuristic: heuristic.name().to_string(),
+            });
+        }
+        self.reset();
+        self.check_solvable()?;
+        self.heuristic = if algorithm.is_informed() {
+            heuristic
+        } else {
+            Box::new(search::Blind)
+        };
+        let start = Instant::now();
+
+        let mut soln = match algorithm {
+            Algorithm::AStar | Algorithm::WeightedAStar { .. } | Algorithm::Greedy | Algorithm::UniformCost => {
+                self.a_star(start)
+            }
+            Algorithm::IdaStar | Algorithm::IterativeDeepening => self.ida_star(start),
+            Algorithm::AraStar { weight } => self.ara_star(weight, start, improved),
+            Algorithm::Rbfs => self.rbfs(start),
+            Algorithm::SmaStar { max_nodes } => self.sma_star(max_nodes, start),
+            Algorithm::Beam { width } => self.beam(width, start),
+            Algorithm::Bidirectional => self.bidirectional(start),
+            Algorithm::BreadthFirst => self.breadth_first(start),
+            Algorithm::DepthFirst { limit } => self.depth_first(limit, start),
+        }?;
+        // the best first searches work out how close they came for themselves, which can be looser than the bound
+        // the algorithm promises. The promise only holds if the heuristic is consistent or boards are reopened,
+        // so otherwise it's left as worked out. ARA* promises a tighter bound with every run, and sets its own.
+        soln.stats.bound = soln.stats.bound.or(algorithm.bound());
+        soln.stats.ratio = match (soln.stats.ratio, soln.stats.bound) {
+            (Some(ratio), Some(bound)) if self.keeps_bound() => Some(ratio.min(bound)),
+            (ratio, bound) => ratio.or(bound),
+        };
+        Ok(soln)
+    }
+
+    /// Chooses the best possible state and moves to it in a loop until the goal state is reached.
+    /// A state popped after a cheaper copy of its board was generated is stale, and is skipped.
+    fn a_star(&mut self, start: Instant) -> Result<Solution, SolveError> {
+        self.seen.insert(self.current().is, self.state);
+        while self.current().is != self.goal_board {
+            self.check_limits(start)?;
+            self.expand();
+
+            self.state = loop {
+                let node = match self.under_consideration.pop() {
+                    Some(candidate) => candidate.node,
+                    None => return Err(SolveError::Exhausted(Box::new(self.stats(start)))),
+                };
+                if self.seen.get(&self.nodes[node as usize].is) == Some(node) {
+                    break node;
+                }
+                self.no_stale += 1;
+            };
+        }
+
+        let (moves, path) = self.trace_soln();
+        let mut stats = self.stats(start);
+        let lower = self.lower_bound().min(moves.len() as isize);
+        stats.ratio = Some(if lower > 0 { moves.len() as f64 / lower as f64 } else { 1.0 });
+        Ok(Solution {
+            cost: moves.len(),
+            moves,
+            path,
+            stats,
+        })
+    }
+
+    /// Whether best first searches keep within `Algorithm::bound`, which needs a consistent heuristic or
+    /// reopening.
+    fn keeps_bound(&self) -> bool {
+        self.reopen || self.heuristic.is_consistent()
+    }
+
+    /// The smallest `g + h` of any state still under consideration, or reached more cheaply after its board
+    /// was expanded. Every path to the goal passes through one of them with the cheapest `g` for its board,
+    /// so as long as the heuristic never overestimates, no solution is shorter than this.
+    fn lower_bound(&self) -> isize {
+        let len = self.goal.tiles().len();
+        let mut tiles = [0; Board::MAX_CELLS];
+        let tiles = &mut tiles[..len];
+
+        let open = self.under_consideration.iter().map(|candidate| candidate.node);
+        let mut lower = isize::MAX;
+        for node in open.chain(self.inconsistent.iter().copied()) {
+            let state = &self.nodes[node as usize];
+            // a stale state has a cheaper copy, which is the one that counts
+            if self.seen.get(&state.is) != Some(node) {
+                continue;
+            }
+            state.is.unpack_into(tiles);
+            lower = lower.min(state.g + self.heuristic.estimate(tiles, &self.goal));
+        }
+        lower
+    }
+
+    /// Gives up with the first limit the search has run into, if any, along with the counters so far.
+    fn check_limits(&self, start: Instant) -> Result<(), SolveError> {
+        let limit = match self.limits {
+            Limits {
+                max_expanded: Some(n),
+                ..
+            } if self.no_expanded >= n => Limit::Expanded(n),
+            Limits {
+                max_time: Some(t), ..
+            } if start.elapsed() >= t => Limit::Time(t),
+            _ => return Ok(()),
+        };
+        Err(SolveError::LimitExceeded {
+            limit,
+            stats: Box::new(self.stats(start)),
+        })
+    }
+
+    /// Collects the counters for a search that began at `start`.
+    fn stats(&self, start: Instant) -> Stats {
+        Stats {
+            expanded: self.no_expanded,
+            generated: self.no_generated,
+            duplicates: self.no_duplicates,
+            stale: self.no_stale,
+            reopened: self.no_reopened,
+            reexpanded: self.no_reexpanded,
+            elapsed: start.elapsed(),
+            ..Stats::default()
+        }
+    }
+
+    /// Traces the path from goal state to root and reverses it.
+    /// Returns the moves taken along with every board on the way, starting from the initial state.
+    pub fn trace_soln(&self) -> (Vec<Move>, Vec<Vec<isize>>) {
+        let mut soln: Vec<Move> = vec![];
+        let len = self.goal.tiles().len();
+        let mut state = self.current();
+        let mut path: Vec<Vec<isize>> = vec![state.is.unpack(len)];
+
+        // the root has no move leading to it, so stop once it's on the path
+        while let Some(kind) = state.kind {
+            soln.push(kind);
+            state = &self.nodes[state.parent as usize];
+            path.push(state.is.unpack(len));
+        }
+
+        soln.reverse();
+        path.reverse();
+        (soln, path)
+    }
+}
+
+/// # The result of a successful search.
+/// ## Properties
+///- `moves`:   The moves that take the initial state to the goal state, in order.
+///- `path`:    Every board along the way, from the initial state to the goal state.
+///- `cost`:    Path cost of the solution, i.e. the number of moves.
+///- `stats`:   Counters describing how much work the search did.
+#[derive(Debug, Clone)]
+pub struct Solution {
+    pub moves: Vec<Move>,
+    pub path: Vec<Vec<isize>>,
+    pub cost: usize,
+    pub stats: Stats,
+}
+
+/// # Counters describing how much work a search did.
+/// ## Properties
+///- `expanded`:    Number of nodes expanded.
+///- `generated`:   Number of nodes generated.
+///- `duplicates`:  Children that weren't generated, because their board had already been reached as cheaply.
+///- `stale`:       States taken off the heap and thrown away, because a cheaper copy had been generated since.
+///- `reopened`:    Boards generated again after being expanded, because a cheaper path to them was found.
+///- `reexpanded`:  Expansions of boards the search had already expanded, i.e. work it repeated.
+///- `bound`:       Most times longer than a shortest solution the algorithm promises, if any (see `Algorithm::bound`).
+///- `ratio`:       Most times longer than a shortest solution the search proved the solution is. `1.0` when it's a shortest one.
+///- `elapsed`:     Wall-clock time spent searching.
+///- `iterations`:  For iterative deepening searches, the threshold and node counts of every iteration.
+/// ## Further details
+/// `ratio` assumes the heuristic never overestimates. It's worked out after the search from the states it left
+/// under consideration, so it's usually much smaller than `bound`, and may be set when `bound` isn't. It's only
+/// capped at `bound` when the promise holds (see `Heuristic::is_consistent`), so with a heuristic that isn't
+/// consistent, like a pattern database, and without reopening, it says how far from the shortest the
+/// solution could really be.
+/// ARA* promises more with every run, so its `bound` is the weight of the run that found the solution.
+///
+/// `reexpanded` is what the memory-bounded searches pay for forgetting. IDA* counts the states the previous
+/// iteration expanded too, RBFS the states it comes back to after backing out of them, and SMA* the states
+/// whose forgotten children it generates again. None of them remember other paths to the same board, so those
+/// aren't counted. A* only expands a board again once it's reopened, and ARA* only counts boards it expands
+/// again within a run, since every run starts afresh.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stats {
+    pub expanded: usize,
+    pub generated: usize,
+    pub duplicates: usize,
+    pub stale: usize,
+    pub reopened: usize,
+    pub reexpanded: usize,
+    pub bound: Option<f64>,
+    pub ratio: Option<f64>,
+    pub elapsed: Duration,
+    pub iterations: Vec<Iteration>,
+}
+
+/// # The same problem solved with two heuristics, to see how much work one saves over the other.
+/// ## Properties
+///- `baseline`: The solution found with the heuristic compared against, like `Manhattan`.
+///- `solution`: The solution found with the heuristic being compared, like `LinearConflict`.
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub baseline: Solution,
+    pub solution: Solution,
+}
+
+impl Comparison {
+    /// How many fewer nodes `solution` expanded than `baseline`, or how many more if it's negative.
+    pub fn fewer_expanded(&self) -> isize {
+        self.baseline.stats.expanded as isize - self.solution.stats.expanded as isize
+    }
+
+    /// How many fewer nodes `solution` generated than `baseline`, or how many more if it's negative.
+    pub fn fewer_generated(&self) -> isize {
+        self.baseline.stats.generated as isize - self.solution.stats.generated as isize
+    }
+}
+
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Expanded {} nodes.\nGenerated {} nodes.\nSolution is {:?}",
+            self.stats.expanded,
+            self.stats.generated,
+            self.moves.iter().map(|m| m.name()).collect::<Vec<_>>()
+        )
+    }
+}
+
+/// Implementing this allows us to use State instances inside print statements
+impl fmt::Debug for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("State")
+            .field("is", &self.is)
+            .field("g", &self.g)
+            .field("kind", &self.kind)
+            .finish()
+    }
+}
+
+impl fmt::Debug for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cols = self.goal.cols();
+        let tiles = self.current().is.unpack(self.goal.tiles().len());
+        let current = Grid { tiles: &tiles, cols }.to_string();
+        let goal = Grid { tiles: self.goal.tiles(), cols }.to_string();
+        let width = current.lines().next().unwrap_or("").len() + 13;
+
+        write!(f, "{:width$}Goal State:", "Current State:", width = width + 1)?;
+        for (c, g) in current.lines().zip(goal.lines()) {
+            write!(f, "\n{:width$}{}", c, g, width = width)?;
+        }
+        Ok(())
+    }
+}
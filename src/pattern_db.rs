@@ -0,0 +1,380 @@
+//! # Additive pattern databases
+//! A pattern database splits the tiles into disjoint groups, and for every placement of a group's tiles
+//! stores how many times those tiles have to move to reach their goal positions, whatever the other tiles
+//! are doing. Only moves of a group's own tiles are counted, so the numbers from different groups can be
+//! added up without ever overestimating the true distance.
+//! ## Building
+//! Each table is filled by a breadth first search backwards from the goal state, over boards where only the
+//! group's tiles and the blank are told apart. Moving the blank into one of the other tiles is free.
+//! ## On-disk format
+//! Tables take a while to build for the 15-puzzle, so they can be saved and loaded again.
+//! All numbers are little endian -
+//!- `b"SPDB"`, then the format version as a `u32`.
+//!- `rows` and `cols` as `u32`, then the goal board, one `u8` per cell.
+//!- The number of groups as a `u32`. For every group, the number of tiles as a `u32`, the tiles as `u8`,
+//!  the length of its table as a `u64`, and the table itself, one `u8` per entry.
+use crate::heuristic::{Goal, Heuristic};
+use crate::board::{neighbours, Board};
+use crate::permutation::{permutations, rank_partial, unrank_partial};
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+const MAGIC: &[u8; 4] = b"SPDB";
+const VERSION: u32 = 1;
+/// Marks entries the search hasn't reached yet.
+const UNSEEN: u8 = u8::MAX;
+/// Largest search space, including the position of the blank, that a single group may have.
+const MAX_STATES: usize = u32::MAX as usize;
+
+/// # Everything that can go wrong when building, saving or loading a pattern database.
+#[derive(Debug)]
+pub enum PatternDbError {
+    /// The blank was put in a group.
+    Blank,
+    /// A tile is in more than one group.
+    Overlap(isize),
+    /// A tile in a group isn't on the board.
+    OutOfRange(isize),
+    /// A group has too many tiles to tabulate.
+    TooLarge { tiles: usize, states: Option<usize> },
+    /// The file isn't a pattern database, or was written by an incompatible version.
+    BadFormat(String),
+    /// Reading or writing the file failed.
+    Io(io::Error),
+}
+
+impl fmt::Display for PatternDbError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatternDbError::Blank => write!(f, "The blank can't be part of a pattern."),
+            PatternDbError::Overlap(t) => write!(f, "Tile {} is in more than one pattern.", t),
+            PatternDbError::OutOfRange(t) => write!(f, "Tile {} isn't on the board.", t),
+            PatternDbError::TooLarge { tiles, .. } => {
+                write!(f, "A pattern of {} tiles is too large to tabulate.", tiles)
+            }
+            PatternDbError::BadFormat(why) => write!(f, "Not a valid pattern database: {}.", why),
+            PatternDbError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for PatternDbError {}
+
+impl From<io::Error> for PatternDbError {
+    fn from(e: io::Error) -> PatternDbError {
+        PatternDbError::Io(e)
+    }
+}
+
+/// # A group of tiles and its table of distances.
+/// ## Properties
+///- `tiles`:   The tiles in the group.
+///- `table`:   Number of moves of these tiles needed to reach the goal, indexed by `rank_partial` of their positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Pattern {
+    tiles: Vec<isize>,
+    table: Vec<u8>,
+}
+
+/// # An admissible heuristic made of disjoint, additive pattern databases.
+/// ## Properties
+///- `goal`:        The goal state the tables were built for.
+///- `patterns`:    The groups of tiles and their tables.
+/// ## Further details
+/// The estimate for a board is the sum of the table entries for every group, so it is only valid for the
+/// goal state the database was built for. Use `load_or_build` to keep the tables in a file between runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PatternDatabase {
+    goal: Goal,
+    patterns: Vec<Pattern>,
+}
+
+impl PatternDatabase {
+    /// Builds tables for the given groups of tiles, none of which may be the blank or appear twice.
+    /// Tiles left out of every group don't contribute to the estimate.
+    pub fn build(goal: &Goal, partitions: &[Vec<isize>]) -> Result<PatternDatabase, PatternDbError> {
+        let mut seen = vec![false; goal.tiles().len()];
+        for &tile in partitions.iter().flatten() {
+            claim(&mut seen, tile)?;
+        }
+
+        let patterns = partitions
+            .iter()
+            .map(|tiles| {
+                Ok(Pattern {
+                    tiles: tiles.clone(),
+                    table: tabulate(goal, tiles)?,
+                })
+            })
+            .collect::<Result<_, PatternDbError>>()?;
+
+        Ok(PatternDatabase {
+            goal: goal.clone(),
+            patterns,
+        })
+    }
+
+    /// Splits the tiles into groups of the given sizes, taking tiles in the order they appear on the goal
+    /// board. For example `&[5, 5, 5]` on the 15-puzzle gives three groups of five tiles, and `&[6, 6, 3]`
+    /// two groups of six and one of three.
+    pub fn partition(goal: &Goal, sizes: &[usize]) -> Vec<Vec<isize>> {
+        let mut tiles = goal.tiles().iter().cloned().filter(|&t| t != 0);
+        sizes
+            .iter()
+            .map(|&n| tiles.by_ref().take(n).collect())
+            .collect()
+    }
+
+    /// The goal state the tables were built for.
+    pub fn goal(&self) -> &Goal {
+        &self.goal
+    }
+
+    /// The groups of tiles the database is made of.
+    pub fn partitions(&self) -> Vec<Vec<isize>> {
+        self.patterns.iter().map(|p| p.tiles.clone()).collect()
+    }
+
+    /// Loads the database from `path` if it holds one for this goal and these groups.
+    /// Otherwise builds it and saves it to `path` for next time.
+    pub fn load_or_build(
+        path: impl AsRef<Path>,
+        goal: &Goal,
+        partitions: &[Vec<isize>],
+    ) -> Result<PatternDatabase, PatternDbError> {
+        let path = path.as_ref();
+        match PatternDatabase::load(path) {
+            Ok(db) if db.goal == *goal && db.partitions() == partitions => return Ok(db),
+            Ok(_) | Err(PatternDbError::BadFormat(_)) => {}
+            Err(PatternDbError::Io(ref e))
+                if e.kind() == io::ErrorKind::NotFound
+                    || e.kind() == io::ErrorKind::UnexpectedEof => {}
+            Err(e) => return Err(e),
+        }
+
+        let db = PatternDatabase::build(goal, partitions)?;
+        db.save(path)?;
+        Ok(db)
+    }
+
+    /// Reads a database saved with `save`.
+    pub fn load(path: impl AsRef<Path>) -> Result<PatternDatabase, PatternDbError> {
+        PatternDatabase::read_from(BufReader::new(File::open(path)?))
+    }
+
+    /// Writes the database to a file at `path`, replacing it if it exists.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), PatternDbError> {
+        let mut w = BufWriter::new(File::create(path)?);
+        self.write_to(&mut w)?;
+        w.flush()?;
+        Ok(())
+    }
+
+    /// Reads a database in the format described at the top of this module.
+    pub fn read_from(mut r: impl Read) -> Result<PatternDatabase, PatternDbError> {
+        let mut magic = [0; 4];
+        r.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(PatternDbError::BadFormat(String::from("wrong magic number")));
+        }
+        let version = read_u32(&mut r)?;
+        if version != VERSION {
+            return Err(PatternDbError::BadFormat(format!(
+                "version {} isn't supported, expected {}",
+                version, VERSION
+            )));
+        }
+
+        let rows = read_u32(&mut r)? as usize;
+        let cols = read_u32(&mut r)? as usize;
+        // checked before anything the size of the board is allocated
+        let cells = match rows.checked_mul(cols) {
+            Some(cells) if cells <= Board::MAX_CELLS => cells,
+            _ => return Err(PatternDbError::BadFormat(format!("a {}x{} board is too large", rows, cols))),
+        };
+        let mut goal = vec![0; cells];
+        r.read_exact(&mut goal)?;
+        let goal: Vec<isize> = goal.into_iter().map(|t| t as isize).collect();
+        let mut sorted = goal.clone();
+        sorted.sort_unstable();
+        if rows < 2 || cols < 2 || sorted.iter().enumerate().any(|(i, &t)| t != i as isize) {
+            return Err(PatternDbError::BadFormat(String::from("invalid goal state")));
+        }
+        let goal = Goal::new(goal, rows, cols);
+
+        let count = read_u32(&mut r)?;
+        let mut patterns = Vec::new();
+        let mut seen = vec![false; cells];
+        for _ in 0..count {
+            let k = read_u32(&mut r)? as usize;
+            if k >= cells {
+                return Err(PatternDbError::BadFormat(format!("a group of {} tiles doesn't fit", k)));
+            }
+            let mut tiles = vec![0; k];
+            r.read_exact(&mut tiles)?;
+            let tiles: Vec<isize> = tiles.into_iter().map(|t| t as isize).collect();
+            for &tile in &tiles {
+                // `estimate` looks every tile up on the board, so one that isn't there can't be let through
+                match claim(&mut seen, tile) {
+                    Ok(()) => {}
+                    Err(PatternDbError::Overlap(_)) => {
+                        return Err(PatternDbError::BadFormat(format!("tile {} is in more than one group", tile)))
+                    }
+                    Err(_) => return Err(PatternDbError::BadFormat(format!("tile {} can't be in a group", tile))),
+                }
+            }
+
+            // no larger than a table `build` could have made, so a bad length can't run out of memory
+            let len = read_u64(&mut r)? as usize;
+            let expected = permutations(cells, k).filter(|p| p.checked_mul(cells).is_some_and(|s| s <= MAX_STATES));
+            if Some(len) != expected {
+                return Err(PatternDbError::BadFormat(String::from("wrong table size")));
+            }
+            let mut table = vec![0; len];
+            r.read_exact(&mut table)?;
+            patterns.push(Pattern { tiles, table });
+        }
+
+        Ok(PatternDatabase { goal, patterns })
+    }
+
+    /// Writes the database in the format described at the top of this module.
+    pub fn write_to(&self, mut w: impl Write) -> Result<(), PatternDbError> {
+        w.write_all(MAGIC)?;
+        w.write_all(&VERSION.to_le_bytes())?;
+        w.write_all(&(self.goal.rows() as u32).to_le_bytes())?;
+        w.write_all(&(self.goal.cols() as u32).to_le_bytes())?;
+        let goal: Vec<u8> = self.goal.tiles().iter().map(|&t| t as u8).collect();
+        w.write_all(&goal)?;
+
+        w.write_all(&(self.patterns.len() as u32).to_le_bytes())?;
+        for p in &self.patterns {
+            w.write_all(&(p.tiles.len() as u32).to_le_bytes())?;
+            let tiles: Vec<u8> = p.tiles.iter().map(|&t| t as u8).collect();
+            w.write_all(&tiles)?;
+            w.write_all(&(p.table.len() as u64).to_le_bytes())?;
+            w.write_all(&p.table)?;
+        }
+        Ok(())
+    }
+}
+
+impl Heuristic for PatternDatabase {
+    fn name(&self) -> &str {
+        "Pattern Database"
+    }
+
+    fn estimate(&self, board: &[isize], goal: &Goal) -> isize {
+        debug_assert_eq!(*goal, self.goal, "pattern database built for another goal");
+        let mut positions = vec![0; board.len()];
+        for (i, &t) in board.iter().enumerate() {
+            positions[t as usize] = i;
+        }
+
+        let n = board.len();
+        let mut placed = Vec::new();
+        self.patterns
+            .iter()
+            .map(|p| {
+                placed.clear();
+                placed.extend(p.tiles.iter().map(|&t| positions[t as usize]));
+                p.table[rank_partial(&placed, n)] as isize
+            })
+            .sum()
+    }
+
+    fn supports(&self, goal: &Goal) -> bool {
+        *goal == self.goal
+    }
+}
+
+/// Marks `tile` as being in a group, checking that it isn't the blank, is on the board, and isn't in another
+/// group already.
+fn claim(seen: &mut [bool], tile: isize) -> Result<(), PatternDbError> {
+    if tile == 0 {
+        return Err(PatternDbError::Blank);
+    }
+    if tile < 0 || tile as usize >= seen.len() {
+        return Err(PatternDbError::OutOfRange(tile));
+    }
+    if seen[tile as usize] {
+        return Err(PatternDbError::Overlap(tile));
+    }
+    seen[tile as usize] = true;
+    Ok(())
+}
+
+/// Fills the table for one group of tiles by a breadth first search from the goal state.
+fn tabulate(goal: &Goal, tiles: &[isize]) -> Result<Vec<u8>, PatternDbError> {
+    let (rows, cols) = (goal.rows(), goal.cols());
+    let n = rows * cols;
+    let k = tiles.len();
+    let too_large = PatternDbError::TooLarge {
+        tiles: k,
+        states: permutations(n, k).and_then(|p| p.checked_mul(n)),
+    };
+    let placements = match permutations(n, k) {
+        Some(p) if p.checked_mul(n).is_some_and(|s| s <= MAX_STATES) => p,
+        _ => return Err(too_large),
+    };
+
+    // Search states are the cells of the group's tiles followed by the blank, indexed by
+    // `rank_partial(tiles) * n + blank`.
+    let mut dist = vec![UNSEEN; placements * n];
+    let mut queue = VecDeque::new();
+    let mut cells: Vec<usize> = tiles.iter().map(|&t| goal.position(t)).collect();
+    let start = rank_partial(&cells, n) * n + goal.position(0);
+    dist[start] = 0;
+    queue.push_back(start as u32);
+
+    while let Some(state) = queue.pop_front() {
+        let state = state as usize;
+        let (placement, blank) = (state / n, state % n);
+        let d = dist[state];
+        unrank_partial(placement, n, &mut cells);
+
+        for &(next, _) in neighbours(blank, cols, n).iter().flatten() {
+            match cells.iter().position(|&c| c == next) {
+                // one of the group's tiles slides into the blank, which costs a move
+                Some(j) => {
+                    cells[j] = blank;
+                    let to = rank_partial(&cells, n) * n + next;
+                    cells[j] = next;
+                    if dist[to] == UNSEEN || dist[to] > d + 1 {
+                        dist[to] = d + 1;
+                        queue.push_back(to as u32);
+                    }
+                }
+                // some other tile moves, which the group doesn't pay for
+                None => {
+                    let to = placement * n + next;
+                    if dist[to] == UNSEEN || dist[to] > d {
+                        dist[to] = d;
+                        queue.push_front(to as u32);
+                    }
+                }
+            }
+        }
+    }
+
+    // where the blank is doesn't matter when looking up a board, so keep the best case
+    Ok(dist
+        .chunks(n)
+        .map(|by_blank| by_blank.iter().cloned().min().unwrap_or(UNSEEN))
+        .collect())
+}
+
+fn read_u32(r: &mut impl Read) -> io::Result<u32> {
+    let mut buf = [0; 4];
+    r.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+fn read_u64(r: &mut impl Read) -> io::Result<u64> {
+    let mut buf = [0; 8];
+    r.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
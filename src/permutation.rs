@@ -0,0 +1,126 @@
+//! # Permutation ranking
+//! Numbers every arrangement of tiles densely, so a board can index straight into a table instead of being
+//! hashed, such as the table of arena indices A* keeps for every board it has seen.
+//!- `rank` and `unrank`:  Every ordering of `n` tiles, numbered `0..n!` by its Lehmer code.
+//!- `rank_partial` and `unrank_partial`: Every placement of `k` distinct tiles on `n` cells, numbered
+//!  `0..n! / (n - k)!`. Pattern databases use these.
+//!- `rank_reachable` and `unrank_reachable`: Every board that can reach a given board, numbered `0..n! / 2`.
+//! ## Further details
+//! The Lehmer code of an ordering lists, for every position, how many of the values after it are smaller.
+//! Reading those digits in a mixed radix of `n`, `n - 1`, ..., `1` gives the rank. All ranks are `usize`, so
+//! boards of up to 20 cells can be ranked on 64-bit targets, which is far more than fits in a table.
+use crate::board::Parity;
+
+/// Number of orderings of `n` tiles, if it fits in a `usize`.
+pub fn factorial(n: usize) -> Option<usize> {
+    permutations(n, n)
+}
+
+/// Number of ways to place `k` distinct tiles on `n` cells, if it fits in a `usize`.
+pub fn permutations(n: usize, k: usize) -> Option<usize> {
+    if k > n {
+        return Some(0);
+    }
+    (n - k + 1..=n).try_fold(1usize, |acc, x| acc.checked_mul(x))
+}
+
+/// Maps a board holding each of `0..n` once to its rank in `0..n!`.
+pub fn rank(board: &[isize]) -> usize {
+    let mut values = [0; 64];
+    let values = &mut values[..board.len()];
+    for (v, &t) in values.iter_mut().zip(board) {
+        *v = t as usize;
+    }
+    rank_partial(values, board.len())
+}
+
+/// Inverse of `rank`, for a board of `n` cells.
+pub fn unrank(index: usize, n: usize) -> Vec<isize> {
+    let mut values = vec![0; n];
+    unrank_partial(index, n, &mut values);
+    values.into_iter().map(|v| v as isize).collect()
+}
+
+/// Maps the cells holding `k` distinct tiles to a dense index in `0..permutations(n, k)`.
+/// Boards may have at most 64 cells.
+pub fn rank_partial(positions: &[usize], n: usize) -> usize {
+    let k = positions.len();
+    let mut index = 0;
+    let mut taken = 0u64;
+    for (i, &p) in positions.iter().enumerate() {
+        // cells taken by earlier tiles can't hold this one, so they're skipped when counting
+        let smaller = (taken & ((1 << p) - 1)).count_ones() as usize;
+        index = index * (n - i) + (p - smaller);
+        taken |= 1 << p;
+    }
+    debug_assert!(k == 0 || index < permutations(n, k).unwrap_or(usize::MAX));
+    index
+}
+
+/// Inverse of `rank_partial`, writing the cells into `positions`.
+pub fn unrank_partial(mut index: usize, n: usize, positions: &mut [usize]) {
+    let k = positions.len();
+    for i in (0..k).rev() {
+        positions[i] = index % (n - i);
+        index /= n - i;
+    }
+    // turn "the p-th free cell" back into an actual cell, front to back
+    let mut used = vec![false; n];
+    for p in positions.iter_mut() {
+        let mut free = *p;
+        let mut cell = 0;
+        loop {
+            if !used[cell] {
+                if free == 0 {
+                    break;
+                }
+                free -= 1;
+            }
+            cell += 1;
+        }
+        used[cell] = true;
+        *p = cell;
+    }
+}
+
+/// # Maps a board to a dense index in `0..n! / 2`, shared only with boards that can't reach it.
+/// The index is the cell of the blank, followed by the rank of the other tiles halved.
+/// ## Further details
+/// Which boards can reach one another depends only on the parity of the inversions among the tiles and on
+/// the row of the blank, as `Parity` explains. Once the blank's cell is known, so is the parity of the
+/// inversions, which is the parity of the sum of the Lehmer digits. Every digit but the second to last has
+/// an even weight, so that digit, which is the lowest bit of the rank, is the only thing halving it drops.
+pub fn rank_reachable(board: &[isize]) -> usize {
+    let n = board.len();
+    let blank = board.iter().position(|&t| t == 0).unwrap_or(0);
+    let mut tiles = [0; 64];
+    let tiles = &mut tiles[..n - 1];
+    for (v, &t) in tiles.iter_mut().zip(board.iter().filter(|&&t| t != 0)) {
+        *v = t as usize - 1;
+    }
+    let half = factorial(n - 1).unwrap_or(usize::MAX) / 2;
+    blank * half + rank_partial(tiles, n - 1) / 2
+}
+
+/// Inverse of `rank_reachable`, giving the board with that index which can reach `like`, a board `cols`
+/// wide.
+pub fn unrank_reachable(index: usize, like: &[isize], cols: usize) -> Vec<isize> {
+    let n = like.len();
+    let half = factorial(n - 1).unwrap_or(usize::MAX) / 2;
+    let (blank, rest) = (index / half, index % half);
+    let odd = Parity::of(like, cols).odd;
+
+    let mut tiles = vec![0; n - 1];
+    let mut board = vec![0; n];
+    for low in 0..2 {
+        unrank_partial(rest * 2 + low, n - 1, &mut tiles);
+        let mut tiles = tiles.iter().map(|&t| t as isize + 1);
+        for (i, cell) in board.iter_mut().enumerate() {
+            *cell = if i == blank { 0 } else { tiles.next().unwrap_or(0) };
+        }
+        if Parity::of(&board, cols).odd == odd {
+            break;
+        }
+    }
+    board
+}
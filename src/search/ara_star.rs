@@ -0,0 +1,140 @@
+//! # Anytime repairing A* (ARA*)
+//! Weighted A* run over and over with a weight that drops every time, as in Likhachev, Gordon and Thrun (2003).
+//! The first run, with the largest weight, finds a solution quickly. Every run after that starts from the
+//! states the last one left under consideration, along with the boards it reached more cheaply after
+//! expanding them, rather than from scratch, so it only repairs what the lower weight changes.
+//! Each run stops once no state under consideration could lead to a shorter solution at its weight, so
+//! the goal state itself is never expanded. Runs go on until the solution is proven to be a shortest one, which
+//! with a heuristic that isn't consistent may take more than one run with a weight of 1.
+use crate::{Board, Candidate, Priority, Problem, Solution, SolveError, Stats};
+use std::time::Instant;
+
+/// Once the weight is within this much of 1, the next run uses 1, and finds a shortest solution.
+const CLOSE_ENOUGH: f64 = 0.1;
+
+impl Problem {
+    /// Runs weighted A* with a weight that starts at `weight` and halves its distance to 1 every run, calling
+    /// `improved` with every solution that's shorter, or proven closer to the shortest, than the last.
+    /// Stops once a solution is proven to be a shortest one, or returns the best one found so far as soon as
+    /// the search runs into one of its limits.
+    pub(crate) fn ara_star(
+        &mut self,
+        mut weight: f64,
+        start: Instant,
+        improved: &mut dyn FnMut(&Solution),
+    ) -> Result<Solution, SolveError> {
+        let goal = self.goal_board;
+        let len = self.goal.tiles().len();
+        let mut tiles = [0; Board::MAX_CELLS];
+        let tiles = &mut tiles[..len];
+
+        let root = self.state;
+        self.seen.insert(self.current().is, root);
+        let h = self.heuristic.estimate(&self.current().is.unpack(len), &self.goal);
+        self.under_consideration.push(Candidate {
+            cost: self.priority.cost(0, h),
+            rank: self.tie_breaking.rank(0, h, 0),
+            node: root,
+        });
+        let mut best: Option<Solution> = None;
+
+        'runs: loop {
+            // weighted A*, until the goal state is at least as good as everything under consideration
+            loop {
+                let reached = self.seen.get(&goal).map(|node| self.priority.cost(self.nodes[node as usize].g, 0));
+                let next = loop {
+                    match self.under_consideration.peek() {
+                        Some(c) if self.seen.get(&self.nodes[c.node as usize].is) != Some(c.node) => {
+                            self.under_consideration.pop();
+                            self.no_stale += 1;
+                        }
+                        top => break top.map(|c| c.cost),
+                    }
+                };
+                match (reached, next) {
+                    (Some(reached), Some(next)) if reached <= next => break,
+                    (Some(_), None) => break,
+                    (None, None) => return Err(SolveError::Exhausted(Box::new(self.stats(start)))),
+                    _ => {}
+                }
+                if let Err(e) = self.check_limits(start) {
+                    if best.is_some() {
+                        break 'runs;
+                    }
+                    return Err(e);
+                }
+
+                self.state = self.under_consideration.pop().map(|c| c.node).unwrap_or(root);
+                self.expand();
+            }
+
+            self.state = self.seen.get(&goal).unwrap_or(root);
+            let (moves, path) = self.trace_soln();
+            let cost = moves.len();
+            // the weight is a tighter bound, but only holds if the heuristic is consistent or boards are reopened
+            let lower = self.lower_bound().min(cost as isize);
+            let mut ratio = if lower > 0 { cost as f64 / lower as f64 } else { 1.0 };
+            if self.keeps_bound() {
+                ratio = ratio.min(weight);
+            }
+            if best
+                .as_ref()
+                .is_none_or(|b| cost < b.cost || b.stats.ratio.is_some_and(|r| ratio < r))
+            {
+                let soln = Solution {
+                    moves,
+                    path,
+                    cost,
+                    stats: Stats {
+                        bound: Some(weight),
+                        ratio: Some(ratio),
+                        ..self.stats(start)
+                    },
+                };
+                improved(&soln);
+                best = Some(soln);
+            }
+            // re-ranking everything under consideration takes a while, so don't start if there's no time left.
+            // A run with a weight of 1 can still end short of a proof, if boards reached more cheaply after they
+            // were expanded could lead somewhere shorter, so those get another run.
+            if ratio <= 1.0 || self.check_limits(start).is_err() {
+                break;
+            }
+
+            weight = 1.0 + (weight - 1.0) / 2.0;
+            if weight - 1.0 < CLOSE_ENOUGH {
+                weight = 1.0;
+            }
+            self.priority = Priority::Weighted(weight);
+            // every board may be expanded again in the next run, starting from the states under consideration
+            // and the ones that were reached more cheaply after their board was expanded
+            for state in &mut self.nodes {
+                state.closed = false;
+            }
+            let mut open: Vec<u32> = self.under_consideration.drain().map(|c| c.node).collect();
+            open.append(&mut self.inconsistent);
+            for node in open {
+                let state = &self.nodes[node as usize];
+                if self.seen.get(&state.is) != Some(node) {
+                    continue;
+                }
+                state.is.unpack_into(tiles);
+                let h = self.heuristic.estimate(tiles, &self.goal);
+                self.under_consideration.push(Candidate {
+                    cost: self.priority.cost(state.g, h),
+                    rank: self.tie_breaking.rank(state.g, h, node as usize),
+                    node,
+                });
+            }
+        }
+
+        // the solution may have been found a few runs ago, but the counters cover every run
+        let mut soln = best.ok_or_else(|| SolveError::Exhausted(Box::new(self.stats(start))))?;
+        soln.stats = Stats {
+            bound: soln.stats.bound,
+            ratio: soln.stats.ratio,
+            ..self.stats(start)
+        };
+        Ok(soln)
+    }
+}
@@ -0,0 +1,86 @@
+//! # Beam search
+//! Breadth first search that only keeps the `width` states closest to the goal, by the heuristic, from every
+//! layer. Everything else is thrown away, so it takes at most `width` expansions per move and finds an answer
+//! on boards far too big for A*, but the answer may be much longer than a shortest one, and a beam that's too
+//! narrow can throw away every path to the goal.
+use crate::board::neighbours;
+use crate::{push_node, Board, Problem, Solution, SolveError, State};
+use std::time::Instant;
+
+impl Problem {
+    /// Expands a layer of at most `width` states at a time, until the goal state is generated.
+    /// Boards that have been kept in an earlier layer are dropped, so the search never goes round in circles.
+    pub(crate) fn beam(&mut self, width: usize, start: Instant) -> Result<Solution, SolveError> {
+        let goal = self.goal_board;
+        let (cols, len) = (self.goal.cols(), self.goal.tiles().len());
+        let mut tiles = [0; Board::MAX_CELLS];
+        let tiles = &mut tiles[..len];
+        self.seen.insert(self.current().is, self.state);
+        let mut layer = vec![self.state];
+
+        'search: while self.current().is != goal {
+            // every child of the layer, along with how far it looks from the goal
+            let mut children = Vec::new();
+            for &parent in &layer {
+                self.check_limits(start)?;
+                let State { is, g, blank, .. } = self.nodes[parent as usize];
+                self.no_expanded += 1;
+                self.nodes[parent as usize].closed = true;
+
+                for &(next, kind) in neighbours(blank as usize, cols, len).iter().flatten() {
+                    let mut child = is;
+                    child.swap(blank as usize, next);
+                    if self.seen.get(&child).is_some() {
+                        self.no_duplicates += 1;
+                        continue;
+                    }
+
+                    self.no_generated += 1;
+                    let node = push_node(
+                        &mut self.nodes,
+                        State {
+                            is: child,
+                            g: g + 1,
+                            parent,
+                            kind: Some(kind),
+                            blank: next as u8,
+                            closed: false,
+                        },
+                    );
+                    if child == goal {
+                        self.state = node;
+                        continue 'search;
+                    }
+                    child.unpack_into(tiles);
+                    children.push((self.heuristic.estimate(tiles, &self.goal), node));
+                }
+            }
+
+            // sorting is stable, so ties go to the child generated first
+            children.sort_by_key(|&(h, _)| h);
+            layer.clear();
+            for (_, node) in children {
+                if layer.len() == width {
+                    break;
+                }
+                // two states in the same layer can share a board, and only one of them is needed
+                let board = self.nodes[node as usize].is;
+                if self.seen.get(&board).is_none() {
+                    self.seen.insert(board, node);
+                    layer.push(node);
+                }
+            }
+            if layer.is_empty() {
+                return Err(SolveError::Exhausted(Box::new(self.stats(start))));
+            }
+        }
+
+        let (moves, path) = self.trace_soln();
+        Ok(Solution {
+            cost: moves.len(),
+            moves,
+            path,
+            stats: self.stats(start),
+        })
+    }
+}
@@ -0,0 +1,246 @@
+//! # Bidirectional search (MM)
+//! Searches forwards from the initial state and backwards from the goal state at the same time, and stops once
+//! the two have met on a path that's provably a shortest one. The backward search estimates how far a board is
+//! from the initial state with the same heuristic, aimed at the initial state instead of the goal.
+//! Both directions order states by `max(g + h, 2g)`, as in MM (Holte et al., 2016), so neither goes much past
+//! the middle of a shortest solution, and the two searches meet halfway.
+use crate::board::neighbours;
+use crate::{push_node, Board, Candidate, Goal, Problem, Seen, Solution, SolveError, State};
+use std::collections::{BinaryHeap, HashSet};
+use std::time::Instant;
+
+/// # One direction of a bidirectional search.
+/// ## Properties
+///- `nodes`:       Every state this direction has generated. Index 0 is the board it started from.
+///- `seen`:        The best state found so far for every board this direction has generated.
+///- `target`:      The board this direction heads towards.
+///- `blind`:       Whether the heuristic can't estimate the distance to `target`, in which case none is used.
+///- `by_priority`: States under consideration, by `max(g + h, 2g)`. The first one is expanded next.
+///- `by_f`:        The same states, by `g + h`.
+///- `by_g`:        The same states, by `g`.
+///- `reopened`:    States for boards this direction had already expanded when it generated them.
+/// ## Further details
+/// A state is only under consideration until it's expanded or a cheaper copy of its board is generated. The
+/// heaps aren't told when that happens, so states that have gone stale are thrown away when they reach the top.
+struct Frontier {
+    nodes: Vec<State>,
+    seen: Seen,
+    target: Goal,
+    blind: bool,
+    by_priority: BinaryHeap<Candidate>,
+    by_f: BinaryHeap<Candidate>,
+    by_g: BinaryHeap<Candidate>,
+    reopened: HashSet<u32>,
+}
+
+impl Frontier {
+    fn new(target: Goal, blind: bool) -> Frontier {
+        Frontier {
+            nodes: Vec::new(),
+            seen: Seen::new(target.tiles().len()),
+            target,
+            blind,
+            by_priority: BinaryHeap::new(),
+            by_f: BinaryHeap::new(),
+            by_g: BinaryHeap::new(),
+            reopened: HashSet::new(),
+        }
+    }
+
+    /// Adds a state to the arena and to the states under consideration, and returns its index.
+    fn push(&mut self, state: State, h: isize, rank: (isize, isize)) -> u32 {
+        let g = state.g;
+        let node = push_node(&mut self.nodes, state);
+        self.seen.insert(state.is, node);
+        self.by_priority.push(Candidate {
+            cost: (g + h).max(2 * g),
+            rank,
+            node,
+        });
+        self.by_f.push(Candidate { cost: g + h, rank, node });
+        self.by_g.push(Candidate { cost: g, rank, node });
+        node
+    }
+
+    /// Throws away stale states from the top of every heap.
+    /// Returns the number thrown away from `by_priority`, which would otherwise have been expanded.
+    fn tidy(&mut self) -> usize {
+        let Frontier {
+            nodes,
+            seen,
+            by_priority,
+            by_f,
+            by_g,
+            ..
+        } = self;
+        let open = |candidate: &Candidate| {
+            let state = &nodes[candidate.node as usize];
+            !state.closed && seen.get(&state.is) == Some(candidate.node)
+        };
+
+        let mut stale = 0;
+        while by_priority.peek().is_some_and(|c| !open(c)) {
+            by_priority.pop();
+            stale += 1;
+        }
+        for heap in [by_f, by_g] {
+            while heap.peek().is_some_and(|c| !open(c)) {
+                heap.pop();
+            }
+        }
+        stale
+    }
+
+    /// Smallest `max(g + h, 2g)`, `g + h` and `g` of the states under consideration, once `tidy` has run.
+    fn least(&self) -> Option<(isize, isize, isize)> {
+        let cost = |heap: &BinaryHeap<Candidate>| heap.peek().map(|c| c.cost);
+        Some((cost(&self.by_priority)?, cost(&self.by_f)?, cost(&self.by_g)?))
+    }
+}
+
+impl Problem {
+    /// Expands whichever direction has the smaller `max(g + h, 2g)` until the two have met on a path no longer
+    /// than any path they could still find.
+    pub(crate) fn bidirectional(&mut self, start: Instant) -> Result<Solution, SolveError> {
+        let len = self.goal.tiles().len();
+        let (rows, cols) = (self.goal.rows(), self.goal.cols());
+        let mut tiles = [0; Board::MAX_CELLS];
+        let tiles = &mut tiles[..len];
+
+        let initial = self.current().is.unpack(len);
+        let back_to = Goal::new(initial.clone(), rows, cols);
+        let blind = !self.heuristic.supports(&back_to);
+        let mut sides = [Frontier::new(self.goal.clone(), false), Frontier::new(back_to, blind)];
+
+        let roots = [
+            (State { closed: false, ..*self.current() }, initial),
+            (
+                State {
+                    is: self.goal_board,
+                    g: 0,
+                    parent: 0,
+                    kind: None,
+                    blank: self.goal.position(0) as u8,
+                    closed: false,
+                },
+                self.goal.tiles().to_vec(),
+            ),
+        ];
+        for (side, (root, board)) in sides.iter_mut().zip(roots) {
+            let h = if side.blind { 0 } else { self.heuristic.estimate(&board, &side.target) };
+            side.push(root, h, self.tie_breaking.rank(0, h, 0));
+        }
+
+        // the shortest path found so far, and the states the two directions met on, forwards then backwards
+        let mut best = if self.current().is == self.goal_board { Some((0, 0, 0)) } else { None };
+        let (forward, backward) = loop {
+            for side in &mut sides {
+                self.no_stale += side.tidy();
+            }
+            let (forwards, backwards) = match (sides[0].least(), sides[1].least()) {
+                (Some(f), Some(b)) => (f, b),
+                // one direction has run out of states, so every path has been found
+                _ => match best {
+                    Some((_, forward, backward)) => break (forward, backward),
+                    None => return Err(SolveError::Exhausted(Box::new(self.stats(start)))),
+                },
+            };
+            // no path that hasn't been found yet can be shorter than this, since every move costs 1
+            let lower = forwards
+                .0
+                .min(backwards.0)
+                .max(forwards.1)
+                .max(backwards.1)
+                .max(forwards.2 + backwards.2 + 1);
+            if let Some((cost, forward, backward)) = best {
+                if cost <= lower {
+                    break (forward, backward);
+                }
+            }
+            self.check_limits(start)?;
+
+            let d = if forwards.0 <= backwards.0 { 0 } else { 1 };
+            let (ahead, behind) = sides.split_at_mut(1);
+            let (this, other) = if d == 0 {
+                (&mut ahead[0], &behind[0])
+            } else {
+                (&mut behind[0], &ahead[0])
+            };
+
+            let parent = this.by_priority.pop().map(|c| c.node).unwrap_or_default();
+            let State { is, g, blank, .. } = this.nodes[parent as usize];
+            this.nodes[parent as usize].closed = true;
+            self.no_expanded += 1;
+            if this.reopened.remove(&parent) {
+                self.no_reexpanded += 1;
+            }
+            let g = g + 1;
+
+            for &(next, kind) in neighbours(blank as usize, cols, len).iter().flatten() {
+                let mut child = is;
+                child.swap(blank as usize, next);
+                let mut reopened = false;
+                if let Some(node) = this.seen.get(&child) {
+                    let prev = &this.nodes[node as usize];
+                    if prev.g <= g {
+                        self.no_duplicates += 1;
+                        continue;
+                    }
+                    // a reopened copy isn't closed until it's expanded, but its board already has been
+                    reopened = prev.closed || this.reopened.contains(&node);
+                    if reopened {
+                        self.no_reopened += 1;
+                    }
+                }
+
+                child.unpack_into(tiles);
+                let h = if this.blind { 0 } else { self.heuristic.estimate(tiles, &this.target) };
+                self.no_generated += 1;
+                let state = State {
+                    is: child,
+                    g,
+                    parent,
+                    kind: Some(kind),
+                    blank: next as u8,
+                    closed: false,
+                };
+                let node = this.push(state, h, self.tie_breaking.rank(g, h, self.no_generated));
+                if reopened {
+                    this.reopened.insert(node);
+                }
+
+                if let Some(meet) = other.seen.get(&child) {
+                    let cost = g + other.nodes[meet as usize].g;
+                    if best.is_none_or(|(shortest, ..)| cost < shortest) {
+                        best = Some(if d == 0 { (cost, node, meet) } else { (cost, meet, node) });
+                    }
+                }
+            }
+        };
+
+        // forwards from the initial state to the board the two directions met on
+        let mut moves = Vec::new();
+        let mut state = &sides[0].nodes[forward as usize];
+        while let Some(kind) = state.kind {
+            moves.push(kind);
+            state = &sides[0].nodes[state.parent as usize];
+        }
+        moves.reverse();
+        // then on to the goal, undoing the moves the backward direction made to get there
+        let mut state = &sides[1].nodes[backward as usize];
+        while let Some(kind) = state.kind {
+            moves.push(kind.inverse());
+            state = &sides[1].nodes[state.parent as usize];
+        }
+
+        let mut blank = self.current().blank as usize;
+        let moves = moves
+            .into_iter()
+            .map(|kind| {
+                blank = kind.apply(blank, cols, len).unwrap_or(blank);
+                (blank, kind)
+            })
+            .collect();
+        Ok(self.replay(moves, self.stats(start)))
+    }
+}
@@ -0,0 +1,68 @@
+//! # Breadth first search
+//! Expands states in the order they were generated, so every state `d` moves from the initial state is
+//! expanded before any state `d + 1` moves away. Every move costs the same, so the first path to reach the
+//! goal state is a shortest one, and the search can stop as soon as it generates the goal rather than waiting
+//! until it's expanded.
+use crate::board::neighbours;
+use crate::{push_node, Problem, Solution, SolveError, State};
+use std::collections::VecDeque;
+use std::time::Instant;
+
+impl Problem {
+    /// Expands states first in, first out, until the goal state is generated.
+    /// Boards that have been generated before are dropped, since the first path to a board is a shortest one.
+    pub(crate) fn breadth_first(&mut self, start: Instant) -> Result<Solution, SolveError> {
+        let goal = self.goal_board;
+        let (cols, len) = (self.goal.cols(), self.goal.tiles().len());
+        self.seen.insert(self.current().is, self.state);
+        let mut frontier = VecDeque::new();
+        frontier.push_back(self.state);
+
+        'search: while self.current().is != goal {
+            self.check_limits(start)?;
+            let parent = match frontier.pop_front() {
+                Some(node) => node,
+                None => return Err(SolveError::Exhausted(Box::new(self.stats(start)))),
+            };
+            let State { is, g, blank, .. } = self.nodes[parent as usize];
+            self.no_expanded += 1;
+            self.nodes[parent as usize].closed = true;
+
+            for &(next, kind) in neighbours(blank as usize, cols, len).iter().flatten() {
+                let mut child = is;
+                child.swap(blank as usize, next);
+                if self.seen.get(&child).is_some() {
+                    self.no_duplicates += 1;
+                    continue;
+                }
+
+                self.no_generated += 1;
+                let node = push_node(
+                    &mut self.nodes,
+                    State {
+                        is: child,
+                        g: g + 1,
+                        parent,
+                        kind: Some(kind),
+                        blank: next as u8,
+                        closed: false,
+                    },
+                );
+                self.seen.insert(child, node);
+                if child == goal {
+                    self.state = node;
+                    continue 'search;
+                }
+                frontier.push_back(node);
+            }
+        }
+
+        let (moves, path) = self.trace_soln();
+        Ok(Solution {
+            cost: moves.len(),
+            moves,
+            path,
+            stats: self.stats(start),
+        })
+    }
+}
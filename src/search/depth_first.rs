@@ -0,0 +1,78 @@
+//! # Depth limited depth first search
+//! Tries every state up to `limit` moves from the initial state, deepest first, until the goal state turns up.
+//! It visits states in the same order as a single iteration of IDA* with a threshold of `limit` and no
+//! heuristic, but keeps the path on a stack of its own, since `limit` can be far deeper than the call stack.
+//! Iterative deepening depth first search is IDA* without a heuristic, so it needs nothing of its own here.
+use crate::board::neighbours;
+use crate::{permutation, Limit, Problem, Solution, SolveError};
+use std::time::Instant;
+
+impl Problem {
+    /// Searches every path of up to `limit` moves, and returns the first one that reaches the goal state.
+    pub(crate) fn depth_first(&mut self, limit: usize, start: Instant) -> Result<Solution, SolveError> {
+        let len = self.goal.tiles().len();
+        let cols = self.goal.cols();
+        let mut board = self.current().is.unpack(len);
+        let root = self.current().blank as usize;
+        // no shortest solution is longer than there are other boards to visit, so going deeper can't help
+        let depth = match permutation::factorial(len) {
+            Some(n) => limit.min(n / 2 - 1),
+            None => limit,
+        };
+
+        if *board == *self.goal.tiles() {
+            return Ok(self.replay(Vec::new(), self.stats(start)));
+        }
+        self.check_limits(start)?;
+        self.no_expanded += 1;
+
+        // the moves on the path to `board`, and for every state along it, the next of its neighbours to try
+        let mut moves = Vec::new();
+        let mut tried = vec![0];
+        let mut cut_off = false;
+        while let Some(&i) = tried.last() {
+            let blank = moves.last().map_or(root, |&(next, _)| next);
+            let options = neighbours(blank, cols, len);
+            if i == options.len() {
+                tried.pop();
+                if moves.pop().is_some() {
+                    let back = moves.last().map_or(root, |&(next, _)| next);
+                    board.swap(blank, back);
+                }
+                continue;
+            }
+            *tried.last_mut().unwrap() += 1;
+
+            let (next, kind) = match options[i] {
+                Some(option) => option,
+                None => continue,
+            };
+            if moves.last().map(|&(_, last)| kind.inverse() == last) == Some(true) {
+                continue;
+            }
+            self.no_generated += 1;
+            if moves.len() == depth {
+                cut_off = true;
+                continue;
+            }
+
+            board.swap(blank, next);
+            moves.push((next, kind));
+            if *board == *self.goal.tiles() {
+                return Ok(self.replay(moves, self.stats(start)));
+            }
+            self.check_limits(start)?;
+            self.no_expanded += 1;
+            tried.push(0);
+        }
+
+        if cut_off {
+            Err(SolveError::LimitExceeded {
+                limit: Limit::Depth(limit),
+                stats: Box::new(self.stats(start)),
+            })
+        } else {
+            Err(SolveError::Exhausted(Box::new(self.stats(start))))
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! # Iterative deepening A*
+//! A depth first search that gives up on any state whose `g + h` is over a threshold. If the goal isn't
+//! found, the threshold is raised to the smallest `g + h` that went over it, and the search starts over.
+//! Only the path to the current state is kept, so memory grows with the depth of the solution rather than
+//! with the number of states generated.
+use super::Iteration;
+use crate::board::{neighbours, Move};
+use crate::{Problem, Solution, SolveError, Stats};
+use std::time::Instant;
+
+/// What searching below a state found.
+pub(super) enum Bound {
+    /// The goal state, with the moves leading to it left on the path.
+    Found,
+    /// Smallest `g + h` that went over the threshold, if any state did.
+    Over(Option<isize>),
+}
+
+impl Problem {
+    /// Runs depth first searches with a growing threshold on `g + h` until the goal state is reached.
+    pub(crate) fn ida_star(&mut self, start: Instant) -> Result<Solution, SolveError> {
+        let mut board = self.current().is.unpack(self.goal.tiles().len());
+        let mut moves = Vec::new();
+        let mut iterations = Vec::new();
+        let mut threshold = self.heuristic.estimate(&board, &self.goal);
+        let mut previous = None;
+
+        loop {
+            let (expanded, generated) = (self.no_expanded, self.no_generated);
+            let blank = self.current().blank as usize;
+            let bound = self.ida_search(&mut board, blank, None, 0, (previous, threshold), &mut moves, start);
+            iterations.push(Iteration {
+                threshold,
+                expanded: self.no_expanded - expanded,
+                generated: self.no_generated - generated,
+            });
+
+            match bound {
+                Ok(Bound::Found) => break,
+                Ok(Bound::Over(Some(next))) => {
+                    previous = Some(threshold);
+                    threshold = next;
+                }
+                Ok(Bound::Over(None)) => {
+                    let mut stats = self.stats(start);
+                    stats.iterations = iterations;
+                    return Err(SolveError::Exhausted(Box::new(stats)));
+                }
+                Err(mut e) => {
+                    if let SolveError::LimitExceeded { ref mut stats, .. } = e {
+                        stats.iterations = iterations;
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        let mut stats = self.stats(start);
+        stats.iterations = iterations;
+        Ok(self.replay(moves, stats))
+    }
+
+    /// Builds the solution that takes `moves` from the initial state, along with the cell the blank moved to
+    /// with each of them.
+    pub(super) fn replay(&self, moves: Vec<(usize, Move)>, stats: Stats) -> Solution {
+        // replay the moves from the initial state to list every board along the way
+        let mut board = self.current().is.unpack(self.goal.tiles().len());
+        let mut blank = self.current().blank as usize;
+        let mut path = vec![board.clone()];
+        for &(next, _) in &moves {
+            board.swap(blank, next);
+            blank = next;
+            path.push(board.clone());
+        }
+
+        Solution {
+            cost: moves.len(),
+            moves: moves.into_iter().map(|(_, kind)| kind).collect(),
+            path,
+            stats,
+        }
+    }
+
+    /// Searches below `board`, which was reached in `g` moves, without going over `threshold`.
+    /// `previous` is the threshold of the last iteration, as long as the path to `board` stayed within it, in
+    /// which case the last iteration expanded `board` too.
+    /// `last` is the move that reached `board`, so its inverse isn't tried straight away.
+    /// Every move on the way to the goal is left in `moves`, along with the cell the blank moved to.
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn ida_search(
+        &mut self,
+        board: &mut [isize],
+        blank: usize,
+        last: Option<Move>,
+        g: isize,
+        (previous, threshold): (Option<isize>, isize),
+        moves: &mut Vec<(usize, Move)>,
+        start: Instant,
+    ) -> Result<Bound, SolveError> {
+        let f = g + self.heuristic.estimate(board, &self.goal);
+        if f > threshold {
+            return Ok(Bound::Over(Some(f)));
+        }
+        if *board == *self.goal.tiles() {
+            return Ok(Bound::Found);
+        }
+        self.check_limits(start)?;
+
+        self.no_expanded += 1;
+        let previous = previous.filter(|&p| f <= p);
+        if previous.is_some() {
+            self.no_reexpanded += 1;
+        }
+        let mut smallest: Option<isize> = None;
+        for &(next, kind) in neighbours(blank, self.goal.cols(), board.len()).iter().flatten() {
+            if Some(kind.inverse()) == last {
+                continue;
+            }
+            self.no_generated += 1;
+
+            board.swap(blank, next);
+            moves.push((next, kind));
+            match self.ida_search(board, next, Some(kind), g + 1, (previous, threshold), moves, start)? {
+                Bound::Found => return Ok(Bound::Found),
+                Bound::Over(over) => {
+                    smallest = match (smallest, over) {
+                        (Some(a), Some(b)) => Some(a.min(b)),
+                        (a, b) => a.or(b),
+                    }
+                }
+            }
+            moves.pop();
+            board.swap(blank, next);
+        }
+
+        Ok(Bound::Over(smallest))
+    }
+}
@@ -0,0 +1,146 @@
+//! # Search algorithms
+//! `Problem::solve_with` can search for the goal state in more than one way.
+//! Every algorithm shares the same move generation, heuristics, limits and `Solution` type.
+//! The uninformed ones - breadth first, depth first, iterative deepening and uniform cost search - ignore the
+//! heuristic, which makes them slow but a useful baseline to compare heuristics against.
+mod ara_star;
+mod beam;
+mod bidirectional;
+mod breadth_first;
+mod depth_first;
+mod ida_star;
+mod rbfs;
+mod sma_star;
+
+use crate::{Goal, Heuristic};
+use std::fmt;
+
+/// # The search algorithms `Problem::solve_with` can use.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Algorithm {
+    /// Best first search on `g + h`, keeping every generated state in memory.
+    AStar,
+    /// Best first search on `g + weight * h`. A weight over 1 trusts the heuristic more than A* does, which
+    /// usually expands far fewer states, and finds a solution at most `weight` times longer than a shortest one.
+    /// The weight can't be less than 1.
+    WeightedAStar { weight: f64 },
+    /// Best first search on `h` alone, heading straight for whichever state looks closest to the goal.
+    /// Usually the fastest, but makes no promise about how long the solution is.
+    Greedy,
+    /// Breadth first search that keeps only the `width` states with the smallest `h` from every layer, and
+    /// returns the first solution it finds. Fast even on big boards, but makes no promise about how long the
+    /// solution is, and gives up with `SolveError::Exhausted` if the beam throws away every path to the goal.
+    /// The width can't be 0.
+    Beam { width: usize },
+    /// Anytime repairing A*. Weighted A* run over and over, starting with `weight` and halving its distance to 1
+    /// every run, reusing the states from the runs before. Finds a first solution as quickly as weighted A*,
+    /// then keeps finding shorter ones, or proving the last one is closer to the shortest, until it's proven to
+    /// be a shortest one. Returns the best found so far when it runs into a limit. `Problem::solve_anytime`
+    /// hands over every solution along the way. The weight can't be less than 1.
+    AraStar { weight: f64 },
+    /// Iterative deepening A*. Repeats a depth first search, cutting off states whose `g + h` is over a
+    /// threshold that grows every iteration. Only keeps the current path in memory.
+    IdaStar,
+    /// Recursive best first search. Expands states in the same order as A*, but keeps only the current path and
+    /// the siblings along it in memory, backing out of a path once it looks worse than the best alternative
+    /// and expanding it again if the search comes back to it.
+    Rbfs,
+    /// Simplified memory-bounded A*. Best first search on `g + h` that never keeps more than `max_nodes` states
+    /// in memory. When it runs out of room, the state with the largest `g + h` is forgotten, and its parent
+    /// remembers how good it was, so it can be generated again if everything else turns out worse. Finds a
+    /// shortest solution if one fits, and gives up with `Limit::Memory` otherwise.
+    SmaStar { max_nodes: usize },
+    /// Bidirectional search (MM). Searches forwards from the initial state and backwards from the goal state,
+    /// with the heuristic aimed at the initial state for the backward direction, until the two meet on a path
+    /// that's provably a shortest one. Heuristics built for one goal, like pattern databases, can't be aimed
+    /// elsewhere, so the backward direction goes without.
+    Bidirectional,
+    /// Expands states in the order they were generated, one depth at a time. Finds a shortest solution.
+    BreadthFirst,
+    /// Follows one path as deep as it goes, up to `limit` moves, before backing up. Keeps only the current
+    /// path in memory, and returns the first solution it finds, which may not be the shortest.
+    DepthFirst { limit: usize },
+    /// Repeats a depth first search with a limit that grows by one every iteration, i.e. IDA* without a
+    /// heuristic. Finds a shortest solution.
+    IterativeDeepening,
+    /// Best first search on `g` alone, i.e. A* without a heuristic. Finds a shortest solution.
+    UniformCost,
+}
+
+impl Algorithm {
+    /// Whether the algorithm uses the heuristic passed to `Problem::solve_with`.
+    pub fn is_informed(self) -> bool {
+        matches!(
+            self,
+            Algorithm::AStar
+                | Algorithm::WeightedAStar { .. }
+                | Algorithm::Greedy
+                | Algorithm::Beam { .. }
+                | Algorithm::AraStar { .. }
+                | Algorithm::IdaStar
+                | Algorithm::Rbfs
+                | Algorithm::SmaStar { .. }
+                | Algorithm::Bidirectional
+        )
+    }
+
+    /// How many times longer than a shortest solution the solution may be, as long as the heuristic never
+    /// overestimates and is consistent (or reopening is on, see `Problem::with_reopening`).
+    /// `None` if there's no such bound.
+    pub fn bound(self) -> Option<f64> {
+        match self {
+            Algorithm::WeightedAStar { weight } | Algorithm::AraStar { weight } => Some(weight.max(1.0)),
+            Algorithm::Greedy | Algorithm::Beam { .. } | Algorithm::DepthFirst { .. } => None,
+            _ => Some(1.0),
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Algorithm::AStar => write!(f, "A*"),
+            Algorithm::WeightedAStar { weight } => write!(f, "A* with a weight of {}", weight),
+            Algorithm::Greedy => write!(f, "greedy best first search"),
+            Algorithm::Beam { width } => write!(f, "beam search with a width of {}", width),
+            Algorithm::AraStar { weight } => write!(f, "ARA* starting from a weight of {}", weight),
+            Algorithm::IdaStar => write!(f, "IDA*"),
+            Algorithm::Rbfs => write!(f, "RBFS"),
+            Algorithm::SmaStar { max_nodes } => write!(f, "SMA* with at most {} nodes", max_nodes),
+            Algorithm::Bidirectional => write!(f, "bidirectional search"),
+            Algorithm::BreadthFirst => write!(f, "breadth first search"),
+            Algorithm::DepthFirst { limit } => write!(f, "depth first search to depth {}", limit),
+            Algorithm::IterativeDeepening => write!(f, "iterative deepening depth first search"),
+            Algorithm::UniformCost => write!(f, "uniform cost search"),
+        }
+    }
+}
+
+/// # One iteration of an iterative deepening search.
+/// ## Properties
+///- `threshold`:   Largest `g + h` allowed during this iteration.
+///- `expanded`:    Number of nodes expanded during this iteration.
+///- `generated`:   Number of nodes generated during this iteration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Iteration {
+    pub threshold: isize,
+    pub expanded: usize,
+    pub generated: usize,
+}
+
+/// The heuristic the uninformed algorithms run with, which takes every board to be at the goal already.
+pub(crate) struct Blind;
+
+impl Heuristic for Blind {
+    fn name(&self) -> &str {
+        "Blind"
+    }
+
+    fn estimate(&self, _board: &[isize], _goal: &Goal) -> isize {
+        0
+    }
+
+    fn is_consistent(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,112 @@
+//! # Recursive best first search (RBFS)
+//! A depth first search that follows whichever child has the smallest `g + h`, and backs out as soon as that
+//! goes over the best alternative higher up the path. Backing out replaces a child's `g + h` with the
+//! smallest one found below it, so the search knows how good the subtree was if it comes back to it later.
+//! Only the current path and the siblings along it are kept, so like IDA* memory grows with the depth of the
+//! solution, but states are expanded in best first order, and a state is expanded again every time the
+//! search comes back to it.
+use super::ida_star::Bound;
+use crate::board::{neighbours, Move};
+use crate::{Problem, Solution, SolveError};
+use std::time::Instant;
+
+/// # A child of the state RBFS is expanding.
+/// ## Properties
+///- `next`:    Cell the blank moves to.
+///- `kind`:    The move that reaches the child.
+///- `f`:       The child's own `g + h`.
+///- `backed`:  Smallest `g + h` found below the child so far, which is never less than `f`.
+#[derive(Clone, Copy)]
+struct Child {
+    next: usize,
+    kind: Move,
+    f: isize,
+    backed: isize,
+}
+
+impl Problem {
+    /// Searches best first from the initial state, keeping only the current path in memory.
+    pub(crate) fn rbfs(&mut self, start: Instant) -> Result<Solution, SolveError> {
+        let mut board = self.current().is.unpack(self.goal.tiles().len());
+        let mut moves = Vec::new();
+        let blank = self.current().blank as usize;
+        let f = self.heuristic.estimate(&board, &self.goal);
+
+        match self.rbfs_search(&mut board, blank, None, 0, (f, f), isize::MAX, &mut moves, start)? {
+            Bound::Found => Ok(self.replay(moves, self.stats(start))),
+            Bound::Over(_) => Err(SolveError::Exhausted(Box::new(self.stats(start)))),
+        }
+    }
+
+    /// Searches below `board`, which was reached in `g` moves, until the goal turns up or every child's
+    /// backed up `g + h` is over `limit`, the best alternative higher up the path.
+    /// `f` is the state's own `g + h` and `backed` the smallest one found below it so far. `backed` being larger
+    /// means the search has been below `board` before, so it's being expanded again.
+    /// `last` is the move that reached `board`, so its inverse isn't tried straight away.
+    /// Every move on the way to the goal is left in `moves`, along with the cell the blank moved to.
+    /// If the goal doesn't turn up, returns the new backed up value, or `None` if there's nothing below `board`.
+    #[allow(clippy::too_many_arguments)]
+    fn rbfs_search(
+        &mut self,
+        board: &mut [isize],
+        blank: usize,
+        last: Option<Move>,
+        g: isize,
+        (f, backed): (isize, isize),
+        limit: isize,
+        moves: &mut Vec<(usize, Move)>,
+        start: Instant,
+    ) -> Result<Bound, SolveError> {
+        if *board == *self.goal.tiles() {
+            return Ok(Bound::Found);
+        }
+        self.check_limits(start)?;
+
+        self.no_expanded += 1;
+        if backed > f {
+            self.no_reexpanded += 1;
+        }
+        let mut children = Vec::with_capacity(4);
+        for &(next, kind) in neighbours(blank, self.goal.cols(), board.len()).iter().flatten() {
+            if Some(kind.inverse()) == last {
+                continue;
+            }
+            self.no_generated += 1;
+
+            board.swap(blank, next);
+            let child = g + 1 + self.heuristic.estimate(board, &self.goal);
+            board.swap(blank, next);
+            // everything below a state that has been searched before is at least as far as it was found to be
+            let inherited = if backed > f { child.max(backed) } else { child };
+            children.push(Child {
+                next,
+                kind,
+                f: child,
+                backed: inherited,
+            });
+        }
+
+        loop {
+            // sorting is stable, so ties go to the child generated first
+            children.sort_by_key(|child| child.backed);
+            let best = match children.first() {
+                Some(&best) if best.backed != isize::MAX => best,
+                _ => return Ok(Bound::Over(None)),
+            };
+            if best.backed > limit {
+                return Ok(Bound::Over(Some(best.backed)));
+            }
+            let alternative = children.get(1).map_or(isize::MAX, |child| child.backed);
+
+            board.swap(blank, best.next);
+            moves.push((best.next, best.kind));
+            let values = (best.f, best.backed);
+            match self.rbfs_search(board, best.next, Some(best.kind), g + 1, values, limit.min(alternative), moves, start)? {
+                Bound::Found => return Ok(Bound::Found),
+                Bound::Over(over) => children[0].backed = over.unwrap_or(isize::MAX),
+            }
+            moves.pop();
+            board.swap(blank, best.next);
+        }
+    }
+}
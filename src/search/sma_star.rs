@@ -0,0 +1,296 @@
+//! # Simplified memory-bounded A* (SMA*)
+//! A* that keeps at most a given number of states in memory. The states are kept as a tree, and when there's
+//! no room for a new one, the leaf with the largest `g + h` is forgotten. Its parent remembers the smallest
+//! `g + h` found below it, so it knows how good that part of the tree was, and generates it again if
+//! everything else turns out to be worse. Every state's `g + h` is backed up to the smallest one below it.
+//! As long as the heuristic never overestimates, and a shortest solution fits in memory along with the path
+//! to it, the solution is a shortest one, just as with A*. Only paths longer than the budget are cut off.
+use crate::board::{neighbours, Move};
+use crate::{push_node, Board, Limit, Problem, Solution, SolveError};
+use std::cmp::Reverse;
+use std::collections::BTreeSet;
+use std::time::Instant;
+
+/// What's known about one of the moves out of a state.
+#[derive(Clone, Copy, PartialEq)]
+enum Slot {
+    /// The move isn't tried, because it's off the board or undoes the move that reached the state.
+    Blocked,
+    /// The state hasn't been expanded yet.
+    Unexplored,
+    /// The child is in memory, at this index.
+    Kept(u32),
+    /// The child was forgotten, and this is the smallest `g + h` that had been found below it.
+    Forgotten(isize),
+}
+
+/// # A state kept in memory by SMA*.
+/// ## Properties
+///- `is`:      The board.
+///- `blank`:   Cell the blank is on.
+///- `g`:       Number of moves from the initial state.
+///- `f`:       Smallest `g + h` found below the state, which is never less than its own.
+///- `parent`:  Index of the state it was generated from. The root is its own parent.
+///- `kind`:    The move that reached it, or `None` for the root.
+///- `slots`:   What's known about each of the moves out of it, in the order `neighbours` lists them.
+#[derive(Clone, Copy)]
+struct Node {
+    is: Board,
+    blank: u8,
+    g: isize,
+    f: isize,
+    parent: u32,
+    kind: Option<Move>,
+    slots: [Slot; 4],
+}
+
+/// Orders states by `f`, and deeper states first among states with the same `f`.
+type Key = (isize, Reverse<isize>, u32);
+
+/// # The states SMA* has in memory.
+/// ## Properties
+///- `nodes`:   Every state in memory, along with the slots of forgotten ones, which are reused.
+///- `free`:    Slots of forgotten states.
+///- `open`:    States with children that haven't been generated or were forgotten, by the smallest `f` of those.
+///- `leaves`:  States other than the root without children in memory, by `f`. The last one is forgotten first.
+struct Memory {
+    nodes: Vec<Node>,
+    free: Vec<u32>,
+    open: BTreeSet<Key>,
+    leaves: BTreeSet<Key>,
+}
+
+impl Memory {
+    /// Number of states in memory.
+    fn len(&self) -> usize {
+        self.nodes.len() - self.free.len()
+    }
+
+    /// Where `node` belongs in `open` and `leaves`, if anywhere.
+    fn keys(&self, node: u32) -> (Option<Key>, Option<Key>) {
+        let state = &self.nodes[node as usize];
+        let key = |f| (f, Reverse(state.g), node);
+        let open = state
+            .slots
+            .iter()
+            .filter_map(|slot| match *slot {
+                Slot::Unexplored => Some(state.f),
+                Slot::Forgotten(f) => Some(f),
+                _ => None,
+            })
+            .min()
+            .map(key);
+        let leaf = state.parent != node && !state.slots.iter().any(|slot| matches!(slot, Slot::Kept(_)));
+        (open, if leaf { Some(key(state.f)) } else { None })
+    }
+
+    /// Takes `node` out of `open` and `leaves`, before it's changed.
+    fn detach(&mut self, node: u32) {
+        let (open, leaf) = self.keys(node);
+        if let Some(key) = open {
+            self.open.remove(&key);
+        }
+        if let Some(key) = leaf {
+            self.leaves.remove(&key);
+        }
+    }
+
+    /// Puts `node` back into `open` and `leaves`, once it's been changed.
+    fn attach(&mut self, node: u32) {
+        let (open, leaf) = self.keys(node);
+        if let Some(key) = open {
+            self.open.insert(key);
+        }
+        if let Some(key) = leaf {
+            self.leaves.insert(key);
+        }
+    }
+
+    /// Adds a state to memory, and returns its index.
+    fn insert(&mut self, state: Node) -> u32 {
+        let node = match self.free.pop() {
+            Some(node) => {
+                self.nodes[node as usize] = state;
+                node
+            }
+            None => push_node(&mut self.nodes, state),
+        };
+        self.attach(node);
+        node
+    }
+
+    /// Changes what `node` knows about its `i`th move.
+    fn set_slot(&mut self, node: u32, i: usize, slot: Slot) {
+        self.detach(node);
+        self.nodes[node as usize].slots[i] = slot;
+        self.attach(node);
+    }
+
+    /// The leaf with the largest `f` other than `keep`, preferring the shallowest, with its `f`.
+    fn worst_leaf(&self, keep: u32) -> Option<(u32, isize)> {
+        let &(f, _, node) = self.leaves.iter().rev().find(|&&(_, _, node)| node != keep)?;
+        Some((node, f))
+    }
+
+    /// Forgets a leaf, and remembers its `f` in its parent.
+    fn forget(&mut self, node: u32) {
+        self.detach(node);
+        self.free.push(node);
+        let Node { parent, f, .. } = self.nodes[node as usize];
+        if let Some(i) = self.nodes[parent as usize].slots.iter().position(|&slot| slot == Slot::Kept(node)) {
+            self.set_slot(parent, i, Slot::Forgotten(f));
+        }
+    }
+
+    /// Sets the `f` of `node` to the smallest `f` below it, and does the same for its ancestors, for as long as
+    /// that changes anything.
+    fn back_up(&mut self, mut node: u32) {
+        loop {
+            let state = &self.nodes[node as usize];
+            if state.slots.contains(&Slot::Unexplored) {
+                return;
+            }
+            let below = state
+                .slots
+                .iter()
+                .filter_map(|slot| match *slot {
+                    Slot::Kept(child) => Some(self.nodes[child as usize].f),
+                    Slot::Forgotten(f) => Some(f),
+                    _ => None,
+                })
+                .min()
+                // nothing below it at all, so there's no solution through it
+                .unwrap_or(isize::MAX);
+            let f = state.f.max(below);
+            if f == state.f {
+                return;
+            }
+
+            self.detach(node);
+            self.nodes[node as usize].f = f;
+            self.attach(node);
+            let parent = self.nodes[node as usize].parent;
+            if parent == node {
+                return;
+            }
+            node = parent;
+        }
+    }
+}
+
+impl Problem {
+    /// Searches like A*, but never keeps more than `max_nodes` states in memory.
+    /// Gives up with `Limit::Memory` if no solution turns up that fits.
+    pub(crate) fn sma_star(&mut self, max_nodes: usize, start: Instant) -> Result<Solution, SolveError> {
+        let (cols, len) = (self.goal.cols(), self.goal.tiles().len());
+        let goal = self.goal_board;
+        let mut tiles = [0; Board::MAX_CELLS];
+        let tiles = &mut tiles[..len];
+
+        let mut memory = Memory {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            open: BTreeSet::new(),
+            leaves: BTreeSet::new(),
+        };
+        let root = self.current();
+        let slots = neighbours(root.blank as usize, cols, len).map(|m| match m {
+            Some(_) => Slot::Unexplored,
+            None => Slot::Blocked,
+        });
+        let h = self.heuristic.estimate(&root.is.unpack(len), &self.goal);
+        memory.insert(Node {
+            is: root.is,
+            blank: root.blank,
+            g: 0,
+            f: h,
+            parent: 0,
+            kind: None,
+            slots,
+        });
+        // whether a path was cut off for want of memory, rather than for leading nowhere
+        let mut cut_off = false;
+
+        let found = loop {
+            let best = match memory.open.iter().next() {
+                Some(&(f, _, node)) if f != isize::MAX => node,
+                _ if cut_off => {
+                    return Err(SolveError::LimitExceeded {
+                        limit: Limit::Memory(max_nodes),
+                        stats: Box::new(self.stats(start)),
+                    })
+                }
+                _ => return Err(SolveError::Exhausted(Box::new(self.stats(start)))),
+            };
+            let Node { is, blank, g, f, .. } = memory.nodes[best as usize];
+            if is == goal {
+                break best;
+            }
+            self.check_limits(start)?;
+
+            self.no_expanded += 1;
+            if memory.nodes[best as usize].slots.iter().any(|slot| matches!(slot, Slot::Forgotten(_))) {
+                self.no_reexpanded += 1;
+            }
+            let moves = neighbours(blank as usize, cols, len);
+            for (i, &(next, kind)) in moves.iter().enumerate().filter_map(|(i, m)| Some((i, m.as_ref()?))) {
+                let below = match memory.nodes[best as usize].slots[i] {
+                    Slot::Unexplored => None,
+                    Slot::Forgotten(below) => Some(below),
+                    _ => continue,
+                };
+                let mut child = is;
+                child.swap(blank as usize, next);
+                child.unpack_into(tiles);
+                self.no_generated += 1;
+                // a child is at least as far from the goal as its parent, and as what was found below it before
+                let h = self.heuristic.estimate(tiles, &self.goal);
+                let child_f = (g + 1 + h).max(f).max(below.unwrap_or(isize::MIN));
+
+                // make room by forgetting the worst leaf, unless the child would be worse than all of them
+                if memory.len() >= max_nodes {
+                    match memory.worst_leaf(best) {
+                        Some((worst, worst_f)) if worst_f >= child_f => memory.forget(worst),
+                        Some(_) => {
+                            memory.set_slot(best, i, Slot::Forgotten(child_f));
+                            continue;
+                        }
+                        // memory is full with the path to `best`, so no path through the child fits
+                        None => {
+                            cut_off = true;
+                            memory.set_slot(best, i, Slot::Forgotten(isize::MAX));
+                            continue;
+                        }
+                    }
+                }
+
+                // the move back to the parent isn't tried, so the search doesn't go straight back
+                let slots = neighbours(next, cols, len).map(|m| match m {
+                    Some((_, back)) if back != kind.inverse() => Slot::Unexplored,
+                    _ => Slot::Blocked,
+                });
+                let node = memory.insert(Node {
+                    is: child,
+                    blank: next as u8,
+                    g: g + 1,
+                    f: child_f,
+                    parent: best,
+                    kind: Some(kind),
+                    slots,
+                });
+                memory.set_slot(best, i, Slot::Kept(node));
+            }
+            memory.back_up(best);
+        };
+
+        // every state on the path to the goal is in memory
+        let mut moves = Vec::new();
+        let mut node = &memory.nodes[found as usize];
+        while let Some(kind) = node.kind {
+            moves.push((node.blank as usize, kind));
+            node = &memory.nodes[node.parent as usize];
+        }
+        moves.reverse();
+        Ok(self.replay(moves, self.stats(start)))
+    }
+}
@@ -0,0 +1,124 @@
+//! Checks that the built-in heuristics never overestimate, and that the ones that say they're consistent are, on
+//! every board small enough to enumerate.
+use project_1_itcs_6156::board::neighbours;
+use project_1_itcs_6156::{
+    check_admissible, DistanceTable, Goal, Hamming, Heuristic, LinearConflict, Manhattan, PatternDatabase,
+};
+use std::collections::{HashSet, VecDeque};
+
+/// The goal with the tiles in order and the blank last.
+fn goal(rows: usize, cols: usize) -> Goal {
+    let len = (rows * cols) as isize;
+    Goal::new((1..len).chain(Some(0)).collect(), rows, cols)
+}
+
+/// Every board size checked, with the number of boards that can reach the goal on it.
+const SIZES: [(usize, usize, usize); 4] = [(2, 2, 12), (2, 3, 360), (2, 4, 20_160), (3, 3, 181_440)];
+
+fn assert_admissible(heuristic: &dyn Heuristic) {
+    for &(rows, cols, boards) in &SIZES {
+        match check_admissible(heuristic, &goal(rows, cols)) {
+            Ok(checked) => assert_eq!(checked, boards),
+            Err(e) => panic!("{} on {}x{}: {}", heuristic.name(), rows, cols, e),
+        }
+    }
+}
+
+/// Checks that the estimate never changes by more than one with a move, on every board that can reach the goal.
+fn assert_consistent(heuristic: &dyn Heuristic, goal: &Goal) {
+    let len = goal.tiles().len();
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    seen.insert(goal.tiles().to_vec());
+    queue.push_back(goal.tiles().to_vec());
+    while let Some(board) = queue.pop_front() {
+        let h = heuristic.estimate(&board, goal);
+        let blank = board.iter().position(|&t| t == 0).unwrap();
+        for &(next, _) in neighbours(blank, goal.cols(), len).iter().flatten() {
+            let mut child = board.clone();
+            child.swap(blank, next);
+            let child_h = heuristic.estimate(&child, goal);
+            assert!((h - child_h).abs() <= 1, "{}: {:?} is {}, {:?} is {}", heuristic.name(), board, h, child, child_h);
+            if seen.insert(child.clone()) {
+                queue.push_back(child);
+            }
+        }
+    }
+}
+
+#[test]
+fn heuristics_that_say_they_are_consistent_are() {
+    let heuristics: [Box<dyn Heuristic>; 3] = [Box::new(Manhattan), Box::new(Hamming), Box::new(LinearConflict)];
+    for &(rows, cols, _) in &SIZES {
+        let goal = goal(rows, cols);
+        for heuristic in &heuristics {
+            assert!(heuristic.is_consistent());
+            assert_consistent(heuristic, &goal);
+        }
+        if let Some(table) = DistanceTable::build(&goal) {
+            assert!(table.is_consistent());
+            assert_consistent(&table, &goal);
+        }
+    }
+}
+
+#[test]
+fn pattern_databases_are_not_consistent() {
+    let goal = goal(3, 3);
+    let partitions = PatternDatabase::partition(&goal, &[4, 4]);
+    let db = PatternDatabase::build(&goal, &partitions).unwrap();
+    assert!(!db.is_consistent());
+}
+
+#[test]
+fn manhattan_is_admissible() {
+    assert_admissible(&Manhattan);
+}
+
+#[test]
+fn hamming_is_admissible() {
+    assert_admissible(&Hamming);
+}
+
+#[test]
+fn linear_conflict_is_admissible() {
+    assert_admissible(&LinearConflict);
+}
+
+#[test]
+fn pattern_database_is_admissible() {
+    for &(rows, cols, _) in &SIZES {
+        let goal = goal(rows, cols);
+        let partitions = PatternDatabase::partition(&goal, &[3, 3]);
+        let db = PatternDatabase::build(&goal, &partitions).unwrap();
+        assert_eq!(check_admissible(&db, &goal).map(|_| ()), Ok(()));
+    }
+}
+
+/// Counts the blank, which the built-in heuristics used to.
+struct ManhattanWithBlank;
+
+impl Heuristic for ManhattanWithBlank {
+    fn name(&self) -> &str {
+        "Manhattan with blank"
+    }
+
+    fn estimate(&self, board: &[isize], goal: &Goal) -> isize {
+        let cols = goal.cols();
+        board
+            .iter()
+            .enumerate()
+            .map(|(i, &t)| {
+                let g = goal.position(t);
+                ((i / cols).abs_diff(g / cols) + (i % cols).abs_diff(g % cols)) as isize
+            })
+            .sum()
+    }
+}
+
+#[test]
+fn counting_the_blank_overestimates() {
+    let e = check_admissible(&ManhattanWithBlank, &goal(3, 3)).unwrap_err();
+    // a single move puts both the blank and one tile a step away from where they belong
+    assert_eq!((e.estimate, e.distance), (2, 1));
+}
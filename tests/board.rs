@@ -0,0 +1,60 @@
+//! Checks packing boards into a `Board` and moving tiles on it, up to the largest board that fits.
+use project_1_itcs_6156::Board;
+
+#[test]
+fn unpacks_what_it_packs() {
+    let boards: [&[isize]; 3] = [
+        &[1, 2, 3, 0],
+        &[8, 6, 7, 2, 5, 4, 3, 0, 1],
+        &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 0],
+    ];
+    for tiles in boards {
+        let board = Board::pack(tiles).unwrap();
+        assert_eq!(board.unpack(tiles.len()), tiles);
+        let mut out = vec![-1; tiles.len()];
+        board.unpack_into(&mut out);
+        assert_eq!(out, tiles);
+        for (i, &t) in tiles.iter().enumerate() {
+            assert_eq!(board.get(i), t);
+        }
+    }
+}
+
+#[test]
+fn packs_25_cells() {
+    // the largest tile ends up in the top bits
+    let tiles: Vec<isize> = (0..25).rev().collect();
+    let board = Board::pack(&tiles).unwrap();
+    assert_eq!(board.unpack(25), tiles);
+    assert_eq!(Board::MAX_CELLS, 25);
+
+    let mut other = tiles.clone();
+    other.swap(0, 24);
+    assert_ne!(Board::pack(&other), Some(board));
+}
+
+#[test]
+fn rejects_boards_that_dont_fit() {
+    let tiles: Vec<isize> = (0..26).collect();
+    assert_eq!(Board::pack(&tiles), None);
+    assert_eq!(Board::pack(&[1, 2, 25, 0]), None);
+    assert_eq!(Board::pack(&[1, 2, -1, 0]), None);
+}
+
+#[test]
+fn swaps_any_two_cells() {
+    let tiles: Vec<isize> = (0..25).collect();
+    for i in 0..25 {
+        for j in 0..25 {
+            let mut board = Board::pack(&tiles).unwrap();
+            board.swap(i, j);
+            let mut expected = tiles.clone();
+            expected.swap(i, j);
+            assert_eq!(board.unpack(25), expected, "swapping {} and {}", i, j);
+
+            // swapping back leaves the board as it was
+            board.swap(j, i);
+            assert_eq!(Some(board), Board::pack(&tiles));
+        }
+    }
+}
@@ -0,0 +1,118 @@
+//! Checks how the command line reads its arguments, and the exit code it reports for each outcome.
+use std::process::{Command, Output};
+
+/// Runs the binary with `args`.
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_project_1_itcs_6156"))
+        .args(args)
+        .output()
+        .expect("couldn't run the binary")
+}
+
+fn code(args: &[&str]) -> i32 {
+    run(args).status.code().expect("killed by a signal")
+}
+
+fn stdout(args: &[&str]) -> String {
+    String::from_utf8(run(args).stdout).unwrap()
+}
+
+fn stderr(args: &[&str]) -> String {
+    String::from_utf8(run(args).stderr).unwrap()
+}
+
+#[test]
+fn help_lists_the_commands() {
+    for help in ["help", "--help", "-h"] {
+        assert_eq!(code(&[help]), 0);
+        assert!(stdout(&[help]).starts_with("Usage:"));
+    }
+}
+
+#[test]
+fn solves_a_board() {
+    let out = stdout(&["solve", "1,2,3,4,5,6,7,0,8", "--format", "json", "--notation", "letters"]);
+    assert!(out.starts_with("{\"status\":\"solved\",\"cost\":1,\"moves\":[\"R\"]"), "{}", out);
+    assert_eq!(code(&["solve", "1,2,3,4,5,6,7,0,8"]), 0);
+}
+
+#[test]
+fn options_can_be_written_with_an_equals_sign() {
+    let spaced = stdout(&["solve", "--start", "1 2 3 4 5 6 0 7 8", "--algorithm", "ida", "--format", "json"]);
+    let joined = stdout(&["solve", "--start=1 2 3 4 5 6 0 7 8", "--algorithm=ida", "--format=json"]);
+    assert!(spaced.contains("\"cost\":2"), "{}", spaced);
+    // only the time taken differs
+    let cut = |s: &str| s[..s.find("elapsed_ms").unwrap()].to_string();
+    assert_eq!(cut(&spaced), cut(&joined));
+}
+
+#[test]
+fn reads_non_square_boards() {
+    assert_eq!(code(&["solve", "1,2,3,4,5,6,0,7", "--rows", "2", "--cols", "4"]), 0);
+    assert_eq!(code(&["check", "1,2,3,4,5,6,0,7", "--cols", "4"]), 0);
+}
+
+#[test]
+fn unsolvable_boards_exit_with_1() {
+    assert_eq!(code(&["check", "2,1,3,4,5,6,7,8,0"]), 1);
+    assert_eq!(code(&["solve", "2,1,3,4,5,6,7,8,0"]), 1);
+}
+
+#[test]
+fn invalid_arguments_exit_with_2() {
+    for args in [
+        &["frobnicate"][..],
+        &["solve"],
+        &["solve", "1,2,3,0", "--frobnicate", "1"],
+        &["solve", "1,2,3,0", "--rows"],
+        &["solve", "1,2,3,x"],
+        &["solve", "1,2,3,3"],
+        &["solve", "1,2,3,0", "1,2,0,3", "0,1,2,3"],
+        &["solve", "1,2,3,0", "--algorithm", "dfs"],
+        &["solve", "1,2,3,0", "--depth", "3"],
+        &["solve", "1,2,3,0", "--algorithm", "weighted", "--weight", "0.5"],
+        &["solve", "1,2,3,0", "--algorithm", "beam", "--width", "0"],
+        &["solve", "1,2,3,0", "--algorithm", "sma", "--max-nodes", "0"],
+        &["solve", "1,2,3,0", "--max-expanded", "-1"],
+        &["solve", "1,2,3,0", "--format", "xml"],
+    ] {
+        assert_eq!(code(args), 2, "{:?}", args);
+    }
+}
+
+#[test]
+fn board_sizes_are_checked_before_anything_else() {
+    assert_eq!(code(&["generate", "--rows", "100000"]), 2);
+    assert_eq!(stderr(&["generate", "--rows", "100000"]), "A board with 100000 rows has more than 25 cells.\n");
+    assert_eq!(code(&["bench", "--cols", "13"]), 2);
+    // one side is enough for a board that fits, but `generate` makes it square
+    assert_eq!(code(&["generate", "--rows", "6"]), 2);
+    assert!(stdout(&["generate", "--rows", "6"]).is_empty());
+    assert!(stderr(&["generate", "--rows", "6"]).starts_with("A 6x6 board isn't supported"));
+    let args = ["solve", "1,2,3,0", "--rows", "4294967296", "--cols", "4294967296"];
+    assert_eq!(code(&args), 2);
+    assert!(stderr(&args).starts_with("A 4294967296x4294967296 board isn't supported"));
+}
+
+#[test]
+fn searches_that_give_up_exit_with_3() {
+    let args = ["solve", "8,6,7,2,5,4,3,0,1", "--max-expanded", "10", "--format", "json"];
+    assert_eq!(code(&args), 3);
+    assert!(stdout(&args).starts_with("{\"status\":\"gave_up\""));
+}
+
+#[test]
+fn unreadable_files_exit_with_4() {
+    assert_eq!(code(&["solve", "--file", "/nonexistent/board.txt"]), 4);
+}
+
+#[test]
+fn the_same_seed_generates_the_same_boards() {
+    let args = ["generate", "--count", "3", "--seed", "42", "--rows", "2", "--cols", "4"];
+    let boards = stdout(&args);
+    assert_eq!(boards.lines().count(), 3);
+    assert_eq!(stdout(&args), boards);
+    for line in boards.lines() {
+        assert_eq!(code(&["check", line, "--rows", "2", "--cols", "4"]), 0);
+    }
+}
@@ -0,0 +1,111 @@
+//! Checks the counters searches keep for boards they reach or expand more than once.
+use project_1_itcs_6156::{Algorithm, LinearConflict, Manhattan, Problem};
+
+const GOAL: [isize; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 0];
+
+/// The two boards furthest from `GOAL`, 31 moves away.
+const HARDEST: [[isize; 9]; 2] = [[8, 6, 7, 2, 5, 4, 3, 0, 1], [6, 4, 7, 8, 5, 0, 3, 2, 1]];
+
+#[test]
+fn every_generated_state_is_expanded_stale_or_left_on_the_heap() {
+    for board in &HARDEST {
+        let mut problem = Problem::new(board.to_vec(), GOAL.to_vec()).unwrap();
+        let stats = problem.solve(Manhattan).unwrap().stats;
+
+        assert!(stats.duplicates > 0);
+        assert!(stats.stale > 0);
+        // the initial state is expanded without being generated
+        assert!(stats.expanded - 1 + stats.stale <= stats.generated);
+    }
+}
+
+#[test]
+fn consistent_heuristics_never_reopen() {
+    for board in &HARDEST {
+        for reopen in [false, true] {
+            let mut problem = Problem::new(board.to_vec(), GOAL.to_vec())
+                .unwrap()
+                .with_reopening(reopen);
+            let soln = problem.solve(LinearConflict).unwrap();
+            assert_eq!(soln.cost, 31);
+            assert_eq!(soln.stats.reopened, 0);
+        }
+    }
+}
+
+#[test]
+fn reopening_changes_nothing_for_a_consistent_heuristic() {
+    let solve = |reopen| {
+        Problem::new(HARDEST[0].to_vec(), GOAL.to_vec())
+            .unwrap()
+            .with_reopening(reopen)
+            .solve(Manhattan)
+            .unwrap()
+    };
+    let (closed, reopened) = (solve(false), solve(true));
+    assert_eq!(closed.moves, reopened.moves);
+    assert_eq!(closed.stats.expanded, reopened.stats.expanded);
+    assert_eq!(closed.stats.generated, reopened.stats.generated);
+}
+
+#[test]
+fn only_the_linear_memory_searches_expand_states_again() {
+    let solve = |algorithm| {
+        Problem::new(HARDEST[0].to_vec(), GOAL.to_vec())
+            .unwrap()
+            .solve_with(algorithm, Manhattan)
+            .unwrap()
+    };
+    assert_eq!(solve(Algorithm::AStar).stats.reexpanded, 0);
+    for algorithm in [Algorithm::IdaStar, Algorithm::Rbfs] {
+        let soln = solve(algorithm);
+        assert_eq!(soln.cost, 31);
+        assert!(soln.stats.reexpanded > 0);
+        assert!(soln.stats.reexpanded < soln.stats.expanded);
+    }
+}
+
+#[test]
+fn ida_star_expands_every_state_of_an_iteration_again_in_the_next() {
+    let soln = Problem::new(HARDEST[1].to_vec(), GOAL.to_vec())
+        .unwrap()
+        .solve_with(Algorithm::IdaStar, Manhattan)
+        .unwrap();
+    let iterations = &soln.stats.iterations;
+    // the last iteration stops at the goal, so it may not get through everything the one before expanded
+    let repeated: usize = iterations[..iterations.len() - 1].iter().map(|i| i.expanded).sum();
+    let last = iterations[iterations.len() - 2].expanded;
+    assert!(soln.stats.reexpanded <= repeated);
+    assert!(soln.stats.reexpanded + last >= repeated);
+}
+
+#[test]
+fn sma_star_generates_forgotten_states_again_on_a_tight_budget() {
+    let solve = |max_nodes| {
+        Problem::new(HARDEST[0].to_vec(), GOAL.to_vec())
+            .unwrap()
+            .solve_with(Algorithm::SmaStar { max_nodes }, Manhattan)
+            .unwrap()
+    };
+    let (roomy, tight) = (solve(100_000), solve(100));
+    assert_eq!(roomy.cost, 31);
+    assert_eq!(tight.cost, 31);
+    assert_eq!(roomy.stats.reexpanded, 0);
+    assert!(tight.stats.reexpanded > 0);
+}
+
+#[test]
+fn solving_again_starts_from_scratch() {
+    let mut problem = Problem::new(HARDEST[0].to_vec(), GOAL.to_vec()).unwrap();
+    let first = problem.solve(Manhattan).unwrap();
+    for algorithm in [Algorithm::IdaStar, Algorithm::Rbfs, Algorithm::Bidirectional, Algorithm::AStar] {
+        let soln = problem.solve_with(algorithm, Manhattan).unwrap();
+        assert_eq!(soln.cost, 31, "{:?}", algorithm);
+        assert_eq!(soln.path[0], HARDEST[0], "{:?}", algorithm);
+    }
+    let again = problem.solve(Manhattan).unwrap();
+    assert_eq!(again.moves, first.moves);
+    assert_eq!(again.stats.expanded, first.stats.expanded);
+    assert_eq!(again.stats.generated, first.stats.generated);
+    assert_eq!(again.stats.stale, first.stats.stale);
+}
@@ -0,0 +1,52 @@
+//! Checks writing and reading moves in every notation.
+use project_1_itcs_6156::{Move, Notation, Problem};
+
+#[test]
+fn inverse_undoes_every_move() {
+    for &m in &Move::ALL {
+        assert_ne!(m.inverse(), m);
+        assert_eq!(m.inverse().inverse(), m);
+        for loc in 0..9 {
+            if let Some(next) = m.apply(loc, 3, 9) {
+                assert_eq!(m.inverse().apply(next, 3, 9), Some(loc));
+            }
+        }
+    }
+}
+
+#[test]
+fn notations() {
+    assert_eq!(Move::Up.to_string(), "Up");
+    assert_eq!(Move::Up.notate(Notation::BlankLetters), "U");
+    assert_eq!(Move::Up.notate(Notation::Tile), "Down");
+    assert_eq!(Move::Left.notate(Notation::TileLetters), "R");
+
+    let moves = [Move::Up, Move::Left, Move::Down, Move::Right];
+    assert_eq!(Notation::Blank.write_all(&moves), "Up Left Down Right");
+    assert_eq!(Notation::BlankLetters.write_all(&moves), "ULDR");
+    assert_eq!(Notation::TileLetters.write_all(&moves), "DRUL");
+    for &notation in &[Notation::Blank, Notation::BlankLetters, Notation::Tile, Notation::TileLetters] {
+        assert_eq!(notation.parse_all(&notation.write_all(&moves)), Ok(moves.to_vec()));
+    }
+}
+
+#[test]
+fn parsing() {
+    assert_eq!("up".parse(), Ok(Move::Up));
+    assert_eq!(" R ".parse(), Ok(Move::Right));
+    assert!("sideways".parse::<Move>().is_err());
+    assert_eq!(Notation::Tile.parse("down"), Ok(Move::Up));
+    assert_eq!(
+        Notation::Blank.parse_all("Up, left  D,r"),
+        Ok(vec![Move::Up, Move::Left, Move::Down, Move::Right])
+    );
+    assert!(Notation::Blank.parse_all("Up, upwards").is_err());
+}
+
+#[test]
+fn solutions_are_moves() {
+    let mut problem =
+        Problem::new(vec![1, 2, 3, 7, 4, 5, 6, 8, 0], vec![1, 2, 3, 8, 6, 4, 7, 5, 0]).unwrap();
+    let soln = problem.solve(project_1_itcs_6156::Manhattan).unwrap();
+    assert_eq!(Notation::BlankLetters.write_all(&soln.moves), "ULDLURDR");
+}
@@ -0,0 +1,507 @@
+//! Checks that searches find shortest solutions, against the distances from a breadth first search over
+//! every board that can reach the 8-puzzle goal.
+use project_1_itcs_6156::board::neighbours;
+use project_1_itcs_6156::{
+    Algorithm, DistanceTable, Goal, Heuristic, Limit, Limits, LinearConflict, Manhattan, PatternDatabase, Problem,
+    Solution, SolveError,
+};
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::sync::OnceLock;
+
+const GOAL: [isize; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 0];
+
+/// Number of moves from every board to `GOAL`, found by breadth first search outwards from it.
+/// Worked out once, and shared by every test.
+fn distances() -> &'static HashMap<Vec<isize>, usize> {
+    static DISTANCES: OnceLock<HashMap<Vec<isize>, usize>> = OnceLock::new();
+    DISTANCES.get_or_init(search_outwards)
+}
+
+fn search_outwards() -> HashMap<Vec<isize>, usize> {
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    distances.insert(GOAL.to_vec(), 0);
+    queue.push_back(GOAL.to_vec());
+
+    while let Some(board) = queue.pop_front() {
+        let d = distances[&board];
+        let blank = board.iter().position(|&t| t == 0).unwrap();
+        for &(next, _) in neighbours(blank, 3, 9).iter().flatten() {
+            let mut child = board.clone();
+            child.swap(blank, next);
+            distances.entry(child.clone()).or_insert_with(|| {
+                queue.push_back(child);
+                d + 1
+            });
+        }
+    }
+    distances
+}
+
+/// Checks that `soln` is a legal sequence of moves from `board` to the goal, `expected` moves long, and that
+/// its moves and path agree.
+fn check(board: &[isize], soln: &Solution, expected: usize) {
+    assert_eq!(soln.cost, expected, "suboptimal solution for {:?}", board);
+    check_path(board, soln);
+}
+
+/// Checks that `soln` is a legal sequence of moves from `board` to the goal, however long, and that its moves
+/// and path agree.
+fn check_path(board: &[isize], soln: &Solution) {
+    assert_eq!(soln.moves.len(), soln.cost);
+    assert_eq!(soln.path.len(), soln.cost + 1);
+    assert_eq!(soln.path[0], board);
+    assert_eq!(soln.path[soln.cost], GOAL);
+    for (pair, m) in soln.path.windows(2).zip(&soln.moves) {
+        let blank = pair[0].iter().position(|&t| t == 0).unwrap();
+        let next = m.apply(blank, 3, 9).expect("move off the board");
+        let mut child = pair[0].clone();
+        child.swap(blank, next);
+        assert_eq!(child, pair[1], "{} doesn't lead from {:?} to {:?}", m, pair[0], pair[1]);
+    }
+}
+
+/// Every `step`-th board that can reach the goal, in order, along with its distance from the goal.
+fn sample(step: usize) -> impl Iterator<Item = (Vec<isize>, usize)> {
+    let distances = distances();
+    assert_eq!(distances.len(), 181_440);
+    let mut boards: Vec<_> = distances.iter().map(|(board, &d)| (board.clone(), d)).collect();
+    boards.sort();
+    boards.into_iter().step_by(step)
+}
+
+fn solve_all(algorithm: Algorithm, heuristic: impl Heuristic + Clone + 'static, step: usize) {
+    solve_within(algorithm, heuristic, usize::MAX, step);
+}
+
+/// Solves the boards `sample(step)` picks that are at most `max_moves` from the goal. The uninformed searches take far
+/// too long on the boards furthest away.
+fn solve_within(algorithm: Algorithm, heuristic: impl Heuristic + Clone + 'static, max_moves: usize, step: usize) {
+    for (board, expected) in sample(step).filter(|&(_, d)| d <= max_moves) {
+        let mut problem = Problem::new(board.clone(), GOAL.to_vec()).unwrap();
+        let soln = problem.solve_with(algorithm, heuristic.clone()).unwrap();
+        check(&board, &soln, expected);
+    }
+}
+
+/// Two pattern databases of four tiles each, so the heuristic isn't exact like a single database of all
+/// eight tiles would be.
+fn pattern_database() -> Rc<PatternDatabase> {
+    let goal = Goal::new(GOAL.to_vec(), 3, 3);
+    let partitions = PatternDatabase::partition(&goal, &[4, 4]);
+    Rc::new(PatternDatabase::build(&goal, &partitions).unwrap())
+}
+
+#[test]
+#[ignore = "solves all 181,440 boards, run with `cargo test --release -- --ignored`"]
+fn a_star_is_optimal_for_every_board() {
+    solve_all(Algorithm::AStar, LinearConflict, 1);
+}
+
+#[test]
+fn a_star_is_optimal() {
+    solve_all(Algorithm::AStar, LinearConflict, 41);
+}
+
+#[test]
+fn a_star_with_manhattan_is_optimal() {
+    solve_all(Algorithm::AStar, Manhattan, 199);
+}
+
+#[test]
+fn linear_conflict_expands_no_more_than_manhattan_on_the_examples() {
+    // the solvable problems the demo binary and the README go through
+    let problems = [
+        ([1, 2, 3, 7, 4, 5, 6, 8, 0], [1, 2, 3, 8, 6, 4, 7, 5, 0]),
+        ([2, 8, 1, 3, 4, 6, 7, 5, 0], [3, 2, 1, 8, 0, 4, 7, 5, 6]),
+        ([0, 1, 3, 4, 2, 5, 7, 8, 6], GOAL),
+    ];
+    for (init_state, goal_state) in problems {
+        let mut problem = Problem::new(init_state.to_vec(), goal_state.to_vec()).unwrap();
+        let comparison = problem.compare(Algorithm::AStar, Manhattan, LinearConflict).unwrap();
+        let (manhattan, linear) = (&comparison.baseline, &comparison.solution);
+        assert_eq!(linear.cost, manhattan.cost);
+        assert!(
+            comparison.fewer_expanded() >= 0,
+            "{:?}: linear conflict expanded {}, Manhattan {}",
+            init_state,
+            linear.stats.expanded,
+            manhattan.stats.expanded
+        );
+        assert_eq!(
+            comparison.fewer_expanded(),
+            manhattan.stats.expanded as isize - linear.stats.expanded as isize
+        );
+    }
+}
+
+#[test]
+fn ida_star_is_optimal() {
+    solve_all(Algorithm::IdaStar, pattern_database(), 41);
+}
+
+#[test]
+fn rbfs_is_optimal() {
+    solve_all(Algorithm::Rbfs, LinearConflict, 397);
+}
+
+#[test]
+fn rbfs_is_optimal_with_a_pattern_database() {
+    solve_all(Algorithm::Rbfs, pattern_database(), 797);
+}
+
+#[test]
+fn sma_star_is_optimal_when_a_solution_fits() {
+    // no 8-puzzle board is more than 31 moves from the goal, so every shortest path fits
+    solve_all(Algorithm::SmaStar { max_nodes: 64 }, LinearConflict, 1999);
+}
+
+#[test]
+fn sma_star_gives_up_when_no_solution_fits() {
+    let goal = vec![1, 2, 3, 4, 5, 0];
+    for board in [vec![0, 5, 4, 3, 2, 1], vec![4, 1, 2, 5, 0, 3], vec![1, 2, 3, 4, 0, 5]] {
+        let solve = |max_nodes| {
+            let mut problem = Problem::with_dimensions(2, 3, board.clone(), goal.clone()).unwrap();
+            problem.solve_with(Algorithm::SmaStar { max_nodes }, Manhattan)
+        };
+        let expected = Problem::with_dimensions(2, 3, board.clone(), goal.clone())
+            .unwrap()
+            .solve_with(Algorithm::BreadthFirst, Manhattan)
+            .unwrap()
+            .cost;
+
+        // a path of `expected` moves takes one more node than that, counting the initial state
+        let soln = solve(expected + 1).unwrap();
+        assert_eq!(soln.cost, expected, "{:?}", board);
+        assert_eq!(soln.path[0], board);
+        assert_eq!(soln.path.last(), Some(&goal));
+        match solve(expected) {
+            Err(SolveError::LimitExceeded { limit, .. }) => assert_eq!(limit, Limit::Memory(expected)),
+            other => panic!("expected to run out of memory, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn bidirectional_is_optimal() {
+    solve_all(Algorithm::Bidirectional, LinearConflict, 397);
+}
+
+#[test]
+fn bidirectional_is_optimal_with_a_heuristic_for_one_goal() {
+    // the pattern database can't estimate the distance back to the initial state, so that direction goes blind
+    solve_all(Algorithm::Bidirectional, pattern_database(), 797);
+}
+
+#[test]
+fn bidirectional_matches_breadth_first_on_2x3_boards() {
+    let goal = vec![1, 2, 3, 4, 5, 0];
+    let mut boards = vec![goal.clone()];
+    let mut i = 0;
+    // every board that can reach the goal, found by moving the blank around
+    while i < boards.len() {
+        let board = boards[i].clone();
+        let blank = board.iter().position(|&t| t == 0).unwrap();
+        for &(next, _) in neighbours(blank, 3, 6).iter().flatten() {
+            let mut child = board.clone();
+            child.swap(blank, next);
+            if !boards.contains(&child) {
+                boards.push(child);
+            }
+        }
+        i += 1;
+    }
+    assert_eq!(boards.len(), 360);
+
+    for board in boards {
+        let solve = |algorithm| {
+            let mut problem = Problem::with_dimensions(2, 3, board.clone(), goal.clone()).unwrap();
+            problem.solve_with(algorithm, Manhattan).unwrap()
+        };
+        let (expected, soln) = (solve(Algorithm::BreadthFirst), solve(Algorithm::Bidirectional));
+        assert_eq!(soln.cost, expected.cost, "{:?}", board);
+        assert_eq!(soln.path.last(), Some(&goal));
+        assert_eq!(soln.path[0], board);
+    }
+}
+
+#[test]
+fn breadth_first_is_optimal() {
+    solve_within(Algorithm::BreadthFirst, Manhattan, 16, 211);
+}
+
+#[test]
+fn uniform_cost_is_optimal() {
+    solve_within(Algorithm::UniformCost, Manhattan, 16, 211);
+}
+
+#[test]
+fn iterative_deepening_is_optimal() {
+    solve_within(Algorithm::IterativeDeepening, Manhattan, 10, 7);
+}
+
+#[test]
+fn depth_first_finds_a_solution_within_its_limit() {
+    for (board, expected) in sample(7).filter(|&(_, d)| d <= 10) {
+        let mut problem = Problem::new(board.clone(), GOAL.to_vec()).unwrap();
+        let soln = problem.solve_with(Algorithm::DepthFirst { limit: expected }, Manhattan).unwrap();
+        check(&board, &soln, expected);
+
+        if expected > 0 {
+            let mut problem = Problem::new(board.clone(), GOAL.to_vec()).unwrap();
+            let limit = expected - 1;
+            match problem.solve_with(Algorithm::DepthFirst { limit }, Manhattan) {
+                Err(SolveError::LimitExceeded { limit: Limit::Depth(n), .. }) => assert_eq!(n, limit),
+                other => panic!("expected to give up at depth {}, got {:?}", limit, other),
+            }
+        }
+    }
+    // a limit far deeper than the call stack goes still finds a solution, if a long way round
+    let board = [1, 2, 3, 4, 5, 6, 0, 7, 8];
+    let mut problem = Problem::new(board.to_vec(), GOAL.to_vec()).unwrap();
+    let soln = problem.solve_with(Algorithm::DepthFirst { limit: 100_000_000 }, Manhattan).unwrap();
+    check_path(&board, &soln);
+}
+
+/// Checks that the solutions are legal, and no longer than `bound` times the shortest, and that the ratio each
+/// search reports is at least what it really is.
+fn solve_within_bound(algorithm: Algorithm, bound: Option<f64>, step: usize) {
+    for (board, expected) in sample(step) {
+        let mut problem = Problem::new(board.clone(), GOAL.to_vec()).unwrap();
+        let soln = problem.solve_with(algorithm, LinearConflict).unwrap();
+        check_path(&board, &soln);
+        assert_eq!(soln.stats.bound, bound);
+
+        let ratio = soln.stats.ratio.unwrap();
+        assert!(
+            soln.cost as f64 <= ratio * expected as f64 + 1e-9,
+            "{:?}: {} moves, ratio {}",
+            board,
+            soln.cost,
+            ratio
+        );
+        if let Some(bound) = bound {
+            assert!(ratio <= bound + 1e-9, "{:?}: ratio {} over bound {}", board, ratio, bound);
+        }
+    }
+}
+
+#[test]
+fn weighted_a_star_is_within_its_weight() {
+    for &weight in &[1.25, 2.0, 5.0] {
+        solve_within_bound(Algorithm::WeightedAStar { weight }, Some(weight), 397);
+    }
+}
+
+#[test]
+fn greedy_reports_how_far_from_optimal_it_is() {
+    solve_within_bound(Algorithm::Greedy, None, 397);
+}
+
+#[test]
+fn weighted_a_star_needs_a_weight_of_at_least_one() {
+    for &weight in &[0.5, -1.0, f64::NAN, f64::INFINITY] {
+        for algorithm in [Algorithm::WeightedAStar { weight }, Algorithm::AraStar { weight }] {
+            let mut problem = Problem::new(GOAL.to_vec(), GOAL.to_vec()).unwrap();
+            match problem.solve_with(algorithm, Manhattan) {
+                Err(SolveError::InvalidWeight(w)) => assert!(w == weight || w.is_nan()),
+                other => panic!("expected a weight of {} to be rejected, got {:?}", weight, other),
+            }
+        }
+    }
+
+    // there's no upper limit, even past where `weight * h` stops fitting in an `isize`
+    let board = [8, 6, 7, 2, 5, 4, 3, 0, 1];
+    for algorithm in [Algorithm::WeightedAStar { weight: 1e17 }, Algorithm::AraStar { weight: 1e17 }] {
+        let mut problem = Problem::new(board.to_vec(), GOAL.to_vec()).unwrap();
+        let soln = problem.solve_with(algorithm, Manhattan).unwrap();
+        check_path(&board, &soln);
+    }
+}
+
+#[test]
+fn ara_star_keeps_improving_until_it_is_optimal() {
+    for (board, expected) in sample(797) {
+        let mut found: Vec<Solution> = Vec::new();
+        let mut problem = Problem::new(board.clone(), GOAL.to_vec()).unwrap();
+        let soln = problem.solve_anytime(3.0, LinearConflict, |soln| found.push(soln.clone())).unwrap();
+
+        for pair in found.windows(2) {
+            let (before, after) = (&pair[0].stats, &pair[1].stats);
+            assert!(pair[1].cost < pair[0].cost || after.ratio < before.ratio, "{:?} didn't improve", board);
+            assert!(after.bound < before.bound);
+        }
+        for soln in &found {
+            check_path(&board, soln);
+            let (bound, ratio) = (soln.stats.bound.unwrap(), soln.stats.ratio.unwrap());
+            assert!(ratio <= bound);
+            assert!(soln.cost as f64 <= ratio * expected as f64 + 1e-9, "{:?}: ratio {}", board, ratio);
+        }
+        check(&board, &soln, expected);
+        assert_eq!(soln.stats.ratio, Some(1.0));
+        assert_eq!(found.last().map(|last| last.cost), Some(expected));
+    }
+}
+
+#[test]
+fn ara_star_is_optimal_with_a_pattern_database() {
+    let heuristic = pattern_database();
+    for (board, expected) in sample(97) {
+        let mut found: Vec<Solution> = Vec::new();
+        let mut problem = Problem::new(board.clone(), GOAL.to_vec()).unwrap();
+        let soln = problem.solve_anytime(3.0, heuristic.clone(), |soln| found.push(soln.clone())).unwrap();
+
+        // the weight and ratio can't be trusted to agree, since the heuristic isn't consistent
+        for soln in &found {
+            check_path(&board, soln);
+            let ratio = soln.stats.ratio.unwrap();
+            assert!(soln.cost as f64 <= ratio * expected as f64 + 1e-9, "{:?}: ratio {}", board, ratio);
+        }
+        check(&board, &soln, expected);
+        assert_eq!(soln.stats.ratio, Some(1.0));
+    }
+}
+
+#[test]
+fn ara_star_returns_the_best_solution_so_far_when_it_runs_out_of_time() {
+    // 31 moves from the goal, and far enough that a weight of 5 doesn't find a shortest solution
+    let board = vec![8, 6, 7, 2, 5, 4, 3, 0, 1];
+    let solve = |max_expanded| {
+        Problem::new(board.clone(), GOAL.to_vec())
+            .unwrap()
+            .with_limits(Limits {
+                max_expanded: Some(max_expanded),
+                ..Limits::default()
+            })
+            .solve_with(Algorithm::AraStar { weight: 5.0 }, Manhattan)
+    };
+
+    let soln = solve(500).unwrap();
+    check_path(&board, &soln);
+    assert!(soln.cost > 31);
+    assert!(soln.stats.ratio.unwrap() > 1.0);
+    assert_eq!(soln.stats.expanded, 500);
+    match solve(1) {
+        Err(SolveError::LimitExceeded { limit, .. }) => assert_eq!(limit, Limit::Expanded(1)),
+        other => panic!("expected to give up before the first solution, got {:?}", other),
+    }
+}
+
+#[test]
+fn beam_search_finds_a_solution_or_says_why_not() {
+    for (board, _) in sample(397) {
+        for width in [1, 10] {
+            let mut problem = Problem::new(board.clone(), GOAL.to_vec()).unwrap();
+            match problem.solve_with(Algorithm::Beam { width }, LinearConflict) {
+                Ok(soln) => {
+                    check_path(&board, &soln);
+                    assert_eq!(soln.stats.bound, None);
+                    // never more than `width` expansions per move
+                    assert!(soln.stats.expanded <= soln.cost * width);
+                }
+                // a beam of one can wander into a corner where every board around it has been kept before
+                Err(SolveError::Exhausted(_)) if width == 1 => {}
+                Err(e) => panic!("{:?} with a width of {}: {}", board, width, e),
+            }
+        }
+    }
+}
+
+#[test]
+fn beam_search_wide_enough_to_keep_everything_is_breadth_first() {
+    solve_within(Algorithm::Beam { width: 181_440 }, Manhattan, 16, 211);
+}
+
+#[test]
+fn beam_search_answers_quickly_on_a_15_puzzle() {
+    // the first of Korf's 100 random instances, 57 moves from the goal
+    let board = vec![14, 13, 15, 7, 11, 12, 9, 5, 6, 0, 2, 1, 4, 8, 10, 3];
+    let goal: Vec<isize> = (0..16).collect();
+    let mut problem = Problem::new(board.clone(), goal.clone()).unwrap();
+    let soln = problem.solve_with(Algorithm::Beam { width: 100 }, LinearConflict).unwrap();
+    assert!(soln.cost >= 57);
+    assert_eq!(soln.path[0], board);
+    assert_eq!(soln.path.last(), Some(&goal));
+    assert!(soln.stats.expanded <= 100 * soln.cost);
+}
+
+#[test]
+fn beam_search_needs_a_width_of_at_least_one() {
+    let mut problem = Problem::new(GOAL.to_vec(), GOAL.to_vec()).unwrap();
+    match problem.solve_with(Algorithm::Beam { width: 0 }, Manhattan) {
+        Err(SolveError::InvalidWidth) => {}
+        other => panic!("expected a width of 0 to be rejected, got {:?}", other),
+    }
+}
+
+#[test]
+fn sma_star_needs_a_budget_of_at_least_one() {
+    let mut problem = Problem::new(GOAL.to_vec(), GOAL.to_vec()).unwrap();
+    match problem.solve_with(Algorithm::SmaStar { max_nodes: 0 }, Manhattan) {
+        Err(SolveError::InvalidBudget) => {}
+        other => panic!("expected a budget of 0 to be rejected, got {:?}", other),
+    }
+}
+
+#[test]
+fn distance_table_matches_breadth_first_search() {
+    let table = DistanceTable::build(&Goal::new(GOAL.to_vec(), 3, 3)).unwrap();
+    for (board, &expected) in distances() {
+        assert_eq!(table.distance(board), expected, "{:?}", board);
+    }
+}
+
+#[test]
+fn a_star_with_distance_table_is_optimal() {
+    let table = Rc::new(DistanceTable::build(&Goal::new(GOAL.to_vec(), 3, 3)).unwrap());
+    solve_all(Algorithm::AStar, table, 41);
+}
+
+#[test]
+fn solved_board_needs_no_moves() {
+    let mut problem = Problem::new(GOAL.to_vec(), GOAL.to_vec()).unwrap();
+    let soln = problem.solve(LinearConflict).unwrap();
+    check(&GOAL, &soln, 0);
+}
+
+#[test]
+fn a_star_with_pattern_database_reopens_and_is_optimal() {
+    let heuristic = pattern_database();
+    let mut reopened = 0;
+    for (board, expected) in sample(83) {
+        let mut problem = Problem::new(board.clone(), GOAL.to_vec()).unwrap().with_reopening(true);
+        let soln = problem.solve_with(Algorithm::AStar, heuristic.clone()).unwrap();
+        check(&board, &soln, expected);
+        // a reopened board is only expanded again if it's popped before the goal
+        assert!(soln.stats.reexpanded <= soln.stats.reopened);
+        reopened += soln.stats.reopened;
+    }
+    // the pattern database is admissible but not consistent, so some boards are reached more cheaply later
+    assert!(reopened > 0);
+}
+
+#[test]
+fn a_star_with_pattern_database_says_when_it_is_not_optimal() {
+    let heuristic = pattern_database();
+    let mut longer = 0;
+    for (board, expected) in sample(83) {
+        let mut problem = Problem::new(board.clone(), GOAL.to_vec()).unwrap();
+        let soln = problem.solve_with(Algorithm::AStar, heuristic.clone()).unwrap();
+        check_path(&board, &soln);
+        let ratio = soln.stats.ratio.unwrap();
+        assert!(
+            soln.cost as f64 <= ratio * expected as f64 + 1e-9,
+            "{:?}: {} moves instead of {}, ratio {}",
+            board,
+            soln.cost,
+            expected,
+            ratio
+        );
+        if soln.cost > expected {
+            longer += 1;
+        }
+    }
+    // without reopening, some solutions are longer than the shortest
+    assert!(longer > 0);
+}
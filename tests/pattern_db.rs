@@ -0,0 +1,86 @@
+//! Checks saving and loading pattern databases, and that damaged files are turned away.
+use project_1_itcs_6156::{Goal, Heuristic, PatternDatabase, PatternDbError};
+
+fn database() -> PatternDatabase {
+    let goal = Goal::new(vec![1, 2, 3, 4, 5, 6, 7, 0], 2, 4);
+    let partitions = PatternDatabase::partition(&goal, &[3, 4]);
+    PatternDatabase::build(&goal, &partitions).unwrap()
+}
+
+fn saved(db: &PatternDatabase) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    db.write_to(&mut bytes).unwrap();
+    bytes
+}
+
+/// The reason `bytes` isn't a valid database.
+fn bad_format(bytes: &[u8]) -> String {
+    match PatternDatabase::read_from(bytes) {
+        Err(PatternDbError::BadFormat(why)) => why,
+        Err(e) => panic!("read failed with {}", e),
+        Ok(_) => panic!("damaged database was loaded"),
+    }
+}
+
+#[test]
+fn reads_back_what_it_writes() {
+    let db = database();
+    let loaded = PatternDatabase::read_from(saved(&db).as_slice()).unwrap();
+    assert_eq!(loaded, db);
+    let board = [0, 3, 1, 4, 7, 2, 6, 5];
+    assert_eq!(loaded.estimate(&board, db.goal()), db.estimate(&board, db.goal()));
+}
+
+#[test]
+fn saves_and_loads_a_file() {
+    let db = database();
+    let path = std::env::temp_dir().join(format!("pattern_db_test_{}.spdb", std::process::id()));
+    db.save(&path).unwrap();
+    let loaded = PatternDatabase::load(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap(), db);
+}
+
+// the header is the magic number, the version, `rows` and `cols`, then the goal, one byte per cell. The first
+// group comes straight after, as the number of tiles and the tiles themselves.
+const ROWS: usize = 8;
+const GOAL: usize = 16;
+const FIRST_TILE: usize = GOAL + 8 + 4 + 4;
+
+#[test]
+fn rejects_other_files() {
+    let mut bytes = saved(&database());
+    bytes[..4].copy_from_slice(b"PNG\0");
+    assert_eq!(bad_format(&bytes), "wrong magic number");
+}
+
+#[test]
+fn rejects_boards_too_large_to_hold() {
+    let mut bytes = saved(&database());
+    bytes[ROWS..ROWS + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    bytes[ROWS + 4..ROWS + 8].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert_eq!(bad_format(&bytes), "a 4294967295x4294967295 board is too large");
+}
+
+#[test]
+fn rejects_tiles_off_the_board() {
+    let mut bytes = saved(&database());
+    bytes[FIRST_TILE] = 8;
+    assert_eq!(bad_format(&bytes), "tile 8 can't be in a group");
+    bytes[FIRST_TILE] = 0;
+    assert_eq!(bad_format(&bytes), "tile 0 can't be in a group");
+}
+
+#[test]
+fn rejects_tiles_in_two_groups() {
+    let mut bytes = saved(&database());
+    bytes[FIRST_TILE + 1] = bytes[FIRST_TILE];
+    assert_eq!(bad_format(&bytes), "tile 1 is in more than one group");
+}
+
+#[test]
+fn rejects_tables_of_the_wrong_size() {
+    let mut bytes = saved(&database());
+    bytes[FIRST_TILE + 3..FIRST_TILE + 11].copy_from_slice(&u64::MAX.to_le_bytes());
+    assert_eq!(bad_format(&bytes), "wrong table size");
+}
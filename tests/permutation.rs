@@ -0,0 +1,63 @@
+//! Checks that ranking boards is a bijection onto the advertised range.
+use project_1_itcs_6156::board::Parity;
+use project_1_itcs_6156::permutation::{
+    factorial, rank, rank_partial, rank_reachable, unrank, unrank_partial, unrank_reachable,
+};
+use project_1_itcs_6156::{DistanceTable, Goal};
+
+#[test]
+fn rank_numbers_every_ordering() {
+    for n in 1..=8 {
+        let count = factorial(n).unwrap();
+        for index in 0..count {
+            assert_eq!(rank(&unrank(index, n)), index);
+        }
+    }
+    assert_eq!(unrank(0, 4), vec![0, 1, 2, 3]);
+    assert_eq!(unrank(23, 4), vec![3, 2, 1, 0]);
+}
+
+#[test]
+fn rank_partial_numbers_every_placement() {
+    let (n, k) = (9, 4);
+    let mut positions = vec![0; k];
+    for index in 0..9 * 8 * 7 * 6 {
+        unrank_partial(index, n, &mut positions);
+        assert_eq!(rank_partial(&positions, n), index);
+    }
+}
+
+#[test]
+fn rank_reachable_numbers_every_reachable_board() {
+    for &(n, cols) in &[(4, 2), (6, 3), (6, 2), (8, 4), (9, 3)] {
+        let goal: Vec<isize> = (1..n as isize).chain(Some(0)).collect();
+        let odd = Parity::of(&goal, cols).odd;
+        let half = factorial(n).unwrap() / 2;
+        let mut seen = vec![false; half];
+
+        for index in 0..factorial(n).unwrap() {
+            let board = unrank(index, n);
+            if Parity::of(&board, cols).odd != odd {
+                continue;
+            }
+            let i = rank_reachable(&board);
+            assert!(i < half && !seen[i], "{:?} collides at {}", board, i);
+            seen[i] = true;
+            assert_eq!(unrank_reachable(i, &goal, cols), board);
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+}
+
+#[test]
+fn distance_table_covers_every_board() {
+    let goal = Goal::new(vec![1, 2, 3, 4, 5, 6, 7, 8, 0], 3, 3);
+    let table = DistanceTable::build(&goal).unwrap();
+    assert_eq!(table.distance(goal.tiles()), 0);
+    // the two hardest 8-puzzle boards
+    assert_eq!(table.distance(&[8, 6, 7, 2, 5, 4, 3, 0, 1]), 31);
+    assert_eq!(table.distance(&[6, 4, 7, 8, 5, 0, 3, 2, 1]), 31);
+
+    let big = Goal::new((1..16).chain(Some(0)).collect(), 4, 4);
+    assert!(DistanceTable::build(&big).is_none());
+}
@@ -0,0 +1,122 @@
+//! Checks that A* breaks ties the way `TieBreaking` says, and so expands states in the same order every run.
+use project_1_itcs_6156::board::neighbours;
+use project_1_itcs_6156::{
+    Goal, Heuristic, Insertion, Limits, LinearConflict, Move, Prefer, Problem, SolveError, TieBreaking,
+};
+use std::collections::{HashMap, VecDeque};
+
+const INITIAL: [isize; 4] = [1, 2, 3, 0];
+
+/// Number of moves from `INITIAL` to every board of the 2x2 puzzle that can reach it, which form a cycle of 12.
+fn distances() -> HashMap<Vec<isize>, isize> {
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    distances.insert(INITIAL.to_vec(), 0);
+    queue.push_back(INITIAL.to_vec());
+    while let Some(board) = queue.pop_front() {
+        let d = distances[&board];
+        let blank = board.iter().position(|&t| t == 0).unwrap();
+        for &(next, _) in neighbours(blank, 2, 4).iter().flatten() {
+            let mut child = board.clone();
+            child.swap(blank, next);
+            distances.entry(child.clone()).or_insert_with(|| {
+                queue.push_back(child);
+                d + 1
+            });
+        }
+    }
+    distances
+}
+
+/// Estimates 6 less the distance from `INITIAL`, so every state on the way to the opposite side of the cycle
+/// has a `g + h` of 6, and every choice A* makes is a tie.
+struct Level(HashMap<Vec<isize>, isize>);
+
+impl Heuristic for Level {
+    fn name(&self) -> &str {
+        "Level"
+    }
+
+    fn estimate(&self, board: &[isize], _goal: &Goal) -> isize {
+        6 - self.0[board]
+    }
+}
+
+/// The moves to the state A* chooses to expand after expanding `expanded` states, when every choice is a tie.
+fn chosen(tie_breaking: TieBreaking, expanded: usize) -> Vec<Move> {
+    let distances = distances();
+    assert_eq!(distances.len(), 12);
+    let goal = distances.iter().find(|&(_, &d)| d == 6).map(|(board, _)| board.clone()).unwrap();
+    let limits = Limits {
+        max_expanded: Some(expanded),
+        ..Limits::default()
+    };
+    let mut problem = Problem::with_dimensions(2, 2, INITIAL.to_vec(), goal)
+        .unwrap()
+        .with_limits(limits)
+        .with_tie_breaking(tie_breaking);
+    match problem.solve(Level(distances)) {
+        Err(SolveError::LimitExceeded { .. }) => problem.trace_soln().0,
+        _ => panic!("search didn't stop at its limit"),
+    }
+}
+
+/// The blank starts in the bottom right corner, and can go up or left from there. Both are generated in
+/// that order, and both go round the cycle, the first anticlockwise and the second clockwise.
+const ANTICLOCKWISE: [Move; 4] = [Move::Up, Move::Left, Move::Down, Move::Right];
+const CLOCKWISE: [Move; 4] = [Move::Left, Move::Up, Move::Right, Move::Down];
+
+#[test]
+fn first_in_first_out_goes_breadth_first() {
+    let fifo = TieBreaking {
+        prefer: None,
+        insertion: Insertion::Fifo,
+    };
+    assert_eq!(chosen(fifo, 1), ANTICLOCKWISE[..1]);
+    assert_eq!(chosen(fifo, 2), CLOCKWISE[..1]);
+    assert_eq!(chosen(fifo, 3), ANTICLOCKWISE[..2]);
+    assert_eq!(chosen(fifo, 4), CLOCKWISE[..2]);
+}
+
+#[test]
+fn last_in_first_out_goes_depth_first() {
+    let lifo = TieBreaking {
+        prefer: None,
+        insertion: Insertion::Lifo,
+    };
+    for expanded in 1..=4 {
+        assert_eq!(chosen(lifo, expanded), CLOCKWISE[..expanded]);
+    }
+}
+
+#[test]
+fn preferring_higher_g_or_lower_h_goes_deeper_first() {
+    // equal `g + h` means the deeper state is also the one with the lower `h`
+    for prefer in [Prefer::HigherG, Prefer::LowerH] {
+        for (insertion, path) in [(Insertion::Fifo, ANTICLOCKWISE), (Insertion::Lifo, CLOCKWISE)] {
+            let tie_breaking = TieBreaking {
+                prefer: Some(prefer),
+                insertion,
+            };
+            for expanded in 1..=4 {
+                assert_eq!(chosen(tie_breaking, expanded), path[..expanded]);
+            }
+        }
+    }
+}
+
+#[test]
+fn repeated_runs_give_the_same_answer() {
+    let solve = || {
+        let mut problem = Problem::new(vec![8, 6, 7, 2, 5, 4, 3, 0, 1], vec![1, 2, 3, 4, 5, 6, 7, 8, 0]).unwrap();
+        let mut soln = problem.solve(LinearConflict).unwrap();
+        soln.stats.elapsed = Default::default();
+        soln
+    };
+    let first = solve();
+    for _ in 0..3 {
+        let again = solve();
+        assert_eq!(again.moves, first.moves);
+        assert_eq!(again.stats, first.stats);
+    }
+}
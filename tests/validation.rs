@@ -0,0 +1,177 @@
+//! Checks the boards and problems `Problem` turns away, before any search starts.
+use project_1_itcs_6156::{
+    Algorithm, DistanceTable, Goal, Limits, Manhattan, Parity, PatternDatabase, Problem, Role, SolveError, Unsolvable,
+};
+
+const GOAL: [isize; 9] = [1, 2, 3, 4, 5, 6, 7, 8, 0];
+
+/// Solves with a limit of no expansions at all, so anything other than an `Unsolvable` error means the search
+/// started before finding out there's no solution.
+fn solve_without_searching(problem: Problem) -> Result<(), SolveError> {
+    let limits = Limits {
+        max_expanded: Some(0),
+        ..Limits::default()
+    };
+    problem.with_limits(limits).solve(Manhattan).map(|_| ())
+}
+
+#[test]
+fn swapping_two_tiles_on_an_odd_width_board_is_unsolvable() {
+    let problem = Problem::new(vec![2, 1, 3, 4, 5, 6, 7, 8, 0], GOAL.to_vec()).unwrap();
+    assert_eq!(
+        solve_without_searching(problem),
+        Err(SolveError::Unsolvable(Unsolvable {
+            initial: Parity {
+                inversions: 1,
+                blank_row: 2,
+                odd: true
+            },
+            goal: Parity {
+                inversions: 0,
+                blank_row: 2,
+                odd: false
+            },
+        }))
+    );
+}
+
+#[test]
+fn moving_the_blank_a_row_on_an_even_width_board_is_unsolvable() {
+    // the same tiles in the same order, but the blank is on a different row
+    let problem =
+        Problem::with_dimensions(2, 4, vec![0, 1, 2, 3, 4, 5, 6, 7], vec![1, 2, 3, 4, 5, 6, 7, 0]).unwrap();
+    assert_eq!(
+        solve_without_searching(problem),
+        Err(SolveError::Unsolvable(Unsolvable {
+            initial: Parity {
+                inversions: 0,
+                blank_row: 0,
+                odd: false
+            },
+            goal: Parity {
+                inversions: 0,
+                blank_row: 1,
+                odd: true
+            },
+        }))
+    );
+}
+
+#[test]
+fn solvable_problems_do_search() {
+    let problem = Problem::new(vec![1, 2, 3, 4, 5, 6, 7, 0, 8], GOAL.to_vec()).unwrap();
+    assert!(matches!(solve_without_searching(problem), Err(SolveError::LimitExceeded { .. })));
+}
+
+/// The error `Problem::new` gives for the initial state `board` and the usual goal.
+fn rejected(board: Vec<isize>) -> SolveError {
+    match Problem::new(board, GOAL.to_vec()) {
+        Ok(_) => panic!("board accepted"),
+        Err(e) => e,
+    }
+}
+
+#[test]
+fn boards_must_fill_the_puzzle() {
+    assert_eq!(
+        Problem::with_dimensions(3, 3, vec![1, 2, 3, 4, 5, 6, 7, 0], GOAL.to_vec()).err(),
+        Some(SolveError::WrongLength {
+            role: Role::Initial,
+            expected: 9,
+            found: 8
+        })
+    );
+}
+
+#[test]
+fn boards_need_a_blank() {
+    assert_eq!(
+        Problem::new(GOAL.to_vec(), vec![1, 2, 3, 4, 5, 6, 7, 8, 9]).err(),
+        Some(SolveError::MissingBlank { role: Role::Goal })
+    );
+}
+
+#[test]
+fn tiles_appear_once() {
+    assert_eq!(
+        rejected(vec![1, 2, 3, 4, 5, 6, 7, 7, 0]),
+        SolveError::DuplicateTile {
+            role: Role::Initial,
+            tile: 7
+        }
+    );
+}
+
+#[test]
+fn both_boards_have_the_same_tiles() {
+    assert_eq!(
+        rejected(vec![1, 2, 3, 4, 5, 6, 7, 9, 0]),
+        SolveError::MismatchedTiles {
+            tile: 9,
+            missing_from: Role::Goal
+        }
+    );
+}
+
+#[test]
+fn tiles_fit_on_the_board() {
+    let board = vec![1, 2, 3, 4, 5, 6, 7, -8, 0];
+    assert_eq!(
+        Problem::new(board.clone(), board).err(),
+        Some(SolveError::OutOfRange {
+            role: Role::Initial,
+            tile: -8
+        })
+    );
+}
+
+#[test]
+fn boards_are_at_least_2x2() {
+    assert_eq!(
+        Problem::with_dimensions(1, 4, vec![1, 2, 3, 0], vec![1, 2, 3, 0]).err(),
+        Some(SolveError::InvalidDimensions { rows: 1, cols: 4 })
+    );
+}
+
+#[test]
+fn boards_too_big_to_count_the_cells_of_are_rejected() {
+    let rows = usize::MAX / 2 + 1;
+    assert_eq!(
+        Problem::with_dimensions(rows, 2, vec![1, 2, 3, 0], vec![1, 2, 3, 0]).err(),
+        Some(SolveError::InvalidDimensions { rows, cols: 2 })
+    );
+    assert_eq!(
+        Problem::with_dimensions(1 << 32, 1 << 32, vec![1, 2, 3, 0], vec![1, 2, 3, 0]).err(),
+        Some(SolveError::InvalidDimensions {
+            rows: 1 << 32,
+            cols: 1 << 32
+        })
+    );
+}
+
+#[test]
+fn tables_built_for_another_goal_are_turned_away() {
+    let built_for = Goal::new(vec![1, 2, 3, 4, 5, 0], 2, 3);
+    let table = DistanceTable::build(&built_for).unwrap();
+    let db = PatternDatabase::build(&built_for, &PatternDatabase::partition(&built_for, &[2, 3])).unwrap();
+    let unsupported = |name: &str| {
+        Err(SolveError::UnsupportedGoal {
+            heuristic: name.to_string(),
+        })
+    };
+
+    // a bigger board
+    let mut problem = Problem::new(vec![1, 2, 3, 4, 5, 6, 7, 0, 8], GOAL.to_vec()).unwrap();
+    assert_eq!(problem.solve(table.clone()).map(|_| ()), unsupported("Distance Table"));
+    assert_eq!(problem.solve(db.clone()).map(|_| ()), unsupported("Pattern Database"));
+
+    // the same board, but another goal
+    let mut problem = Problem::with_dimensions(2, 3, vec![1, 2, 3, 4, 0, 5], vec![1, 2, 3, 4, 0, 5]).unwrap();
+    assert_eq!(problem.solve(table.clone()).map(|_| ()), unsupported("Distance Table"));
+    assert_eq!(problem.solve(db.clone()).map(|_| ()), unsupported("Pattern Database"));
+    // searches that don't use a heuristic don't mind
+    assert!(problem.solve_with(Algorithm::BreadthFirst, db).is_ok());
+
+    let mut problem = Problem::with_dimensions(2, 3, vec![1, 2, 3, 4, 0, 5], vec![1, 2, 3, 4, 5, 0]).unwrap();
+    assert_eq!(problem.solve(table).unwrap().cost, 1);
+}